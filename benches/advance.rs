@@ -0,0 +1,159 @@
+#![feature(test)]
+
+//! Benchmarks of the distributed advancement of the global epoch with varying
+//! numbers of threads.
+//!
+//! The thresholds can be set with the `DEBRA_CHECK_THRESHOLD` and
+//! `DEBRA_ADVANCE_THRESHOLD` environment variables.
+//! Each benchmark reports the achieved epoch advances per second and the
+//! average number of operations (guard creations) per advance.
+
+extern crate test;
+
+use std::env;
+use std::sync::atomic::{
+    AtomicBool, AtomicU64,
+    Ordering::{Relaxed, SeqCst},
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use test::Bencher;
+
+use crossbeam_utils::thread::scope;
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+const STEPS: usize = 10_000;
+const STALL: Duration = Duration::from_micros(100);
+
+fn init_config() {
+    let threshold = |var: &str, default: u32| {
+        env::var(var).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+    };
+
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(threshold("DEBRA_CHECK_THRESHOLD", 128))
+            .advance_threshold(threshold("DEBRA_ADVANCE_THRESHOLD", 0))
+            .build()
+    });
+}
+
+/// Returns the current epoch as observed by the calling thread.
+fn current_epoch() -> u64 {
+    drop(Guard::new());
+    Debra::pending_by_epoch()[0].0
+}
+
+/// Creates `STEPS` guards on each of `threads` threads, of which `stalled`
+/// threads hold each guard for a while, and returns the number of observed
+/// epoch advances.
+fn advance(threads: usize, stalled: usize) -> u64 {
+    let start = current_epoch();
+    let latest = AtomicU64::new(start);
+    let done = AtomicBool::new(false);
+
+    scope(|s| {
+        for _ in 0..stalled {
+            s.spawn(|_| {
+                while !done.load(Relaxed) {
+                    let _guard = Guard::new();
+                    thread::sleep(STALL);
+                }
+            });
+        }
+
+        let workers: Vec<_> = (0..threads - stalled)
+            .map(|_| {
+                s.spawn(|_| {
+                    for _ in 0..STEPS {
+                        let _guard = Guard::new();
+                    }
+
+                    latest.fetch_max(current_epoch(), SeqCst);
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        done.store(true, Relaxed);
+    })
+    .unwrap();
+
+    latest.load(SeqCst) - start
+}
+
+fn bench_advance(b: &mut Bencher, threads: usize, stalled: usize) {
+    init_config();
+
+    let mut advances = 0;
+    let mut runs = 0;
+    let start = Instant::now();
+    b.iter(|| {
+        advances += advance(threads, stalled);
+        runs += 1;
+    });
+    let elapsed = start.elapsed();
+
+    let ops = (runs * (threads - stalled) * STEPS) as f64;
+    println!(
+        "\n{:3} threads ({} stalled): {:10.1} advances/s, {:10.1} ops/advance",
+        threads,
+        stalled,
+        advances as f64 / elapsed.as_secs_f64(),
+        ops / advances.max(1) as f64
+    );
+}
+
+#[bench]
+fn advance_001(b: &mut Bencher) {
+    bench_advance(b, 1, 0);
+}
+
+#[bench]
+fn advance_002(b: &mut Bencher) {
+    bench_advance(b, 2, 0);
+}
+
+#[bench]
+fn advance_004(b: &mut Bencher) {
+    bench_advance(b, 4, 0);
+}
+
+#[bench]
+fn advance_008(b: &mut Bencher) {
+    bench_advance(b, 8, 0);
+}
+
+#[bench]
+fn advance_016(b: &mut Bencher) {
+    bench_advance(b, 16, 0);
+}
+
+#[bench]
+fn advance_032(b: &mut Bencher) {
+    bench_advance(b, 32, 0);
+}
+
+#[bench]
+fn advance_064(b: &mut Bencher) {
+    bench_advance(b, 64, 0);
+}
+
+#[bench]
+fn advance_128(b: &mut Bencher) {
+    bench_advance(b, 128, 0);
+}
+
+#[bench]
+fn advance_016_stalled_02(b: &mut Bencher) {
+    bench_advance(b, 16, 2);
+}
+
+#[bench]
+fn advance_016_stalled_08(b: &mut Bencher) {
+    bench_advance(b, 16, 8);
+}
@@ -8,7 +8,7 @@ use std::sync::atomic::Ordering::Relaxed;
 use test::Bencher;
 
 use crossbeam_utils::thread::scope;
-use debra::{ConfigBuilder, Guard, CONFIG};
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
 
 type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
 
@@ -50,6 +50,33 @@ fn multi_pin(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn per_op_guards_1000(b: &mut Bencher) {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(128).advance_threshold(0).build());
+    let atomic = Atomic::new(1);
+
+    b.iter(|| {
+        for _ in 0..1000 {
+            let guard = &Guard::new();
+            assert_eq!(*atomic.load(Relaxed, guard).unwrap(), 1);
+        }
+    })
+}
+
+#[bench]
+fn batched_guard_1000(b: &mut Bencher) {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(128).advance_threshold(0).build());
+    let atomic = Atomic::new(1);
+
+    b.iter(|| {
+        Debra::pin_batch(|guard| {
+            for _ in 0..1000 {
+                assert_eq!(*atomic.load(Relaxed, guard).unwrap(), 1);
+            }
+        })
+    })
+}
+
 #[bench]
 fn pin_and_load(b: &mut Bencher) {
     let atomic = Atomic::new(1);
@@ -0,0 +1,48 @@
+#![feature(test)]
+
+//! Benchmarks of the registration of many threads at once, e.g. at program
+//! start, when the global thread list is still empty.
+
+extern crate test;
+
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use test::Bencher;
+
+use debra::Guard;
+
+fn register_storm(b: &mut Bencher, threads: usize) {
+    b.iter(|| {
+        let barrier = Arc::new(Barrier::new(threads));
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    // the first guard of a thread registers it in the global thread list
+                    drop(Guard::new());
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    });
+}
+
+#[bench]
+fn register_2(b: &mut Bencher) {
+    register_storm(b, 2);
+}
+
+#[bench]
+fn register_8(b: &mut Bencher) {
+    register_storm(b, 8);
+}
+
+#[bench]
+fn register_32(b: &mut Bencher) {
+    register_storm(b, 32);
+}
@@ -6,7 +6,7 @@ use std::sync::atomic::Ordering::Relaxed;
 
 use test::Bencher;
 
-use debra::{ConfigBuilder, CONFIG};
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
 
 type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
 type Owned<T> = debra::Owned<T, debra::typenum::U0>;
@@ -23,6 +23,33 @@ fn retire(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn retire_loop_256(b: &mut Bencher) {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(128).advance_threshold(0).build());
+
+    let global = Atomic::new(1);
+
+    b.iter(|| {
+        for _ in 0..256 {
+            let unlinked = global.swap(Owned::new(1), Relaxed).unwrap();
+            unsafe { unlinked.retire() };
+        }
+    });
+}
+
+#[bench]
+fn retire_batch_256(b: &mut Bencher) {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(128).advance_threshold(0).build());
+
+    let global = Atomic::new(1);
+    let mut batch = Vec::with_capacity(256);
+
+    b.iter(|| {
+        batch.extend((0..256).map(|_| global.swap(Owned::new(1), Relaxed).unwrap()));
+        unsafe { Debra::retire_batch(batch.drain(..)) };
+    });
+}
+
 #[bench]
 fn retire_varied(b: &mut Bencher) {
     CONFIG.init_once(|| ConfigBuilder::new().check_threshold(128).advance_threshold(0).build());
@@ -37,3 +64,17 @@ fn retire_varied(b: &mut Bencher) {
         arr.swap(Owned::new([0usize; 16]), Relaxed).unwrap().retire();
     });
 }
+
+#[bench]
+fn retire_and_pin(b: &mut Bencher) {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(128).advance_threshold(0).build());
+
+    let global = Atomic::new(1);
+
+    // with only a single registered thread, advancing the epoch skips the thread scan
+    b.iter(|| {
+        let _guard = Guard::new();
+        let unlinked = global.swap(Owned::new(1), Relaxed).unwrap();
+        unsafe { unlinked.retire() };
+    });
+}
@@ -68,6 +68,33 @@ pub(crate) struct Iter {
     curr: Option<NonNull<Sealed>>,
 }
 
+/***** impl inherent ******************************************************************************/
+
+#[cfg(feature = "numa")]
+impl Iter {
+    /// Creates a new empty [`Iter`].
+    #[inline]
+    pub fn empty() -> Self {
+        Self { curr: None }
+    }
+
+    /// Returns `true` if the iterator has no more elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.curr.is_none()
+    }
+}
+
+/***** impl From *********************************************************************************/
+
+impl From<SealedList> for Iter {
+    #[inline]
+    fn from(sealed: SealedList) -> Self {
+        let (head, _) = sealed.into_inner();
+        Self { curr: Some(head) }
+    }
+}
+
 /***** impl Iterator ******************************************************************************/
 
 impl Iterator for Iter {
@@ -0,0 +1,65 @@
+//! Extensions for atomic pointers of the DEBRA reclamation scheme.
+
+use core::sync::atomic::Ordering;
+
+use crate::typenum::Unsigned;
+use crate::{Atomic, Owned};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// AtomicExt (trait)
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// An extension trait for [`Atomic`] pointers of the [`Debra`][crate::Debra]
+/// reclamation scheme.
+///
+/// Since [`Atomic`][reclaim::Atomic] is defined in the `reclaim` crate, its
+/// methods can not be extended directly.
+pub trait AtomicExt<T, N: Unsigned> {
+    /// Stores `new` and retires the previous value, if it was not null,
+    /// through the global reclaimer.
+    ///
+    /// This is a shorthand for `atomic.swap(new, order)` followed by
+    /// [`retire`][reclaim::Unlinked::retire] on the unlinked value.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for
+    /// [`retire`][reclaim::GlobalReclaim::retire] apply, i.e. the previous
+    /// value must not be reachable through any other atomic pointer.
+    unsafe fn swap_and_retire(&self, new: Owned<T, N>, order: Ordering)
+    where
+        T: 'static;
+
+    /// Stores `new` and retires the previous value, if it was not null,
+    /// through the global reclaimer, even if `T` is not `'static`.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for
+    /// [`retire_unchecked`][reclaim::GlobalReclaim::retire_unchecked] apply,
+    /// i.e. additionally, the drop of the previous value must not access any
+    /// non-static references, which may have expired by the time it is
+    /// reclaimed.
+    unsafe fn swap_and_retire_unchecked(&self, new: Owned<T, N>, order: Ordering);
+}
+
+/********** impl AtomicExt ************************************************************************/
+
+impl<T, N: Unsigned> AtomicExt<T, N> for Atomic<T, N> {
+    #[inline]
+    unsafe fn swap_and_retire(&self, new: Owned<T, N>, order: Ordering)
+    where
+        T: 'static,
+    {
+        if let Some(unlinked) = self.swap(new, order) {
+            unlinked.retire();
+        }
+    }
+
+    #[inline]
+    unsafe fn swap_and_retire_unchecked(&self, new: Owned<T, N>, order: Ordering) {
+        if let Some(unlinked) = self.swap(new, order) {
+            unlinked.retire_unchecked();
+        }
+    }
+}
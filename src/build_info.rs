@@ -0,0 +1,70 @@
+//! Information about the compile-time configuration of the crate.
+
+use cfg_if::cfg_if;
+
+use crate::config::{DEFAULT_ADVANCE_THRESHOLD, DEFAULT_CHECK_THRESHOLD};
+use crate::local::BAG_QUEUE_COUNT;
+
+cfg_if! {
+    if #[cfg(feature = "bag-size-1")] {
+        const BAG_SIZE: usize = 1;
+    } else if #[cfg(feature = "bag-size-2")] {
+        const BAG_SIZE: usize = 2;
+    } else if #[cfg(feature = "bag-size-4")] {
+        const BAG_SIZE: usize = 4;
+    } else if #[cfg(feature = "bag-size-8")] {
+        const BAG_SIZE: usize = 8;
+    } else if #[cfg(feature = "bag-size-16")] {
+        const BAG_SIZE: usize = 16;
+    } else if #[cfg(feature = "bag-size-32")] {
+        const BAG_SIZE: usize = 32;
+    } else if #[cfg(feature = "bag-size-64")] {
+        const BAG_SIZE: usize = 64;
+    } else if #[cfg(feature = "bag-size-128")] {
+        const BAG_SIZE: usize = 128;
+    } else if #[cfg(feature = "bag-size-512")] {
+        const BAG_SIZE: usize = 512;
+    } else {
+        // the default bag size of `debra-common`
+        const BAG_SIZE: usize = 256;
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// BuildInfo
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The compile-time configuration of the crate, e.g. for diagnosing diverging
+/// behaviour of binaries, which have been compiled with different features.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BuildInfo {
+    /// Whether the crate has been compiled with the `std` feature.
+    pub std: bool,
+    /// The number of records each bag can hold before a new one is allocated.
+    pub bag_size: usize,
+    /// The number of epoch bag queues of each thread.
+    pub bag_queue_count: usize,
+    /// The number of bits available for representing epochs.
+    pub epoch_width_bits: u32,
+    /// The default check threshold, if none is configured.
+    pub default_check_threshold: u32,
+    /// The default advance threshold, if none is configured.
+    pub default_advance_threshold: u32,
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl BuildInfo {
+    /// Creates a new [`BuildInfo`] from the compiled-in constants and features.
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            std: cfg!(feature = "std"),
+            bag_size: BAG_SIZE,
+            bag_queue_count: BAG_QUEUE_COUNT,
+            epoch_width_bits: crate::global::epoch_width_bits(),
+            default_check_threshold: DEFAULT_CHECK_THRESHOLD,
+            default_advance_threshold: DEFAULT_ADVANCE_THRESHOLD,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Injection of randomized yields into the reclamation hot paths.
+//!
+//! When enabled by calling [`configure`], threads randomly yield at certain
+//! points during retiring records, advancing the global epoch and rotating
+//! their epoch bags, in order to provoke rare thread interleavings.
+//! The random decisions of each thread are derived from a global seed, so
+//! that failing runs can be reported and repeated by their seed.
+
+use std::cell::Cell;
+use std::sync::atomic::{
+    AtomicU32, AtomicU64,
+    Ordering::{Acquire, Relaxed, Release},
+};
+use std::thread;
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The global seed from which all thread local seeds are derived.
+static SEED: AtomicU64 = AtomicU64::new(0);
+/// The counter for deriving distinct seeds for each thread.
+static THREAD_COUNT: AtomicU64 = AtomicU64::new(0);
+/// The (inverse) probability of yielding at each yield point, zero disables
+/// yielding.
+static YIELD_ONE_IN: AtomicU32 = AtomicU32::new(0);
+
+thread_local!(static RNG: Cell<Option<u64>> = Cell::new(None));
+
+/// Enables random yielding with the given `seed`, each yield point yields with
+/// a probability of `1 / one_in`.
+///
+/// Passing `0` for `one_in` disables random yielding.
+/// Threads, which have already passed any yield point, keep their previously
+/// derived seed.
+#[inline]
+pub fn configure(seed: u64, one_in: u32) {
+    SEED.store(seed, Relaxed);
+    THREAD_COUNT.store(0, Relaxed);
+    YIELD_ONE_IN.store(one_in, Release);
+}
+
+/// Randomly yields the current thread, if enabled.
+#[inline]
+pub(crate) fn maybe_yield() {
+    let one_in = YIELD_ONE_IN.load(Acquire);
+    if one_in == 0 {
+        return;
+    }
+
+    let rand = RNG.with(|rng| {
+        let state = rng.get().unwrap_or_else(|| {
+            let idx = THREAD_COUNT.fetch_add(1, Relaxed) + 1;
+            SEED.load(Relaxed) ^ idx.wrapping_mul(GOLDEN_GAMMA)
+        });
+
+        let next = xorshift(state);
+        rng.set(Some(next));
+        next
+    });
+
+    if rand % u64::from(one_in) == 0 {
+        thread::yield_now();
+    }
+}
+
+/// Advances the given (non-zero) `state` of a xorshift pseudo random number
+/// generator.
+#[inline]
+fn xorshift(mut state: u64) -> u64 {
+    if state == 0 {
+        state = GOLDEN_GAMMA;
+    }
+
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    state
+}
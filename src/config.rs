@@ -1,10 +1,13 @@
+use core::fmt;
+
 #[cfg(feature = "std")]
 use conquer_once::spin::OnceCell;
 #[cfg(not(feature = "std"))]
 use conquer_once::OnceCell;
 
-const DEFAULT_CHECK_THRESHOLD: u32 = 100;
-const DEFAULT_ADVANCE_THRESHOLD: u32 = 100;
+pub(crate) const DEFAULT_CHECK_THRESHOLD: u32 = 100;
+pub(crate) const DEFAULT_ADVANCE_THRESHOLD: u32 = 100;
+const DEFAULT_ACTIVE_RETIRE_WARN: u32 = 100_000;
 
 /// Global configuration for the reclamation scheme.
 pub static CONFIG: OnceCell<Config> = OnceCell::new();
@@ -18,6 +21,17 @@ pub static CONFIG: OnceCell<Config> = OnceCell::new();
 pub struct Config {
     check_threshold: u32,
     advance_threshold: u32,
+    eager_rotation: bool,
+    guard_lifetime_warn: u32,
+    active_retire_warn: u32,
+    min_abandon_records: u32,
+    adaptive: bool,
+    reclaim_byte_budget: usize,
+    quiescent_advance: bool,
+    global_memory_budget: usize,
+    max_buffered_records: usize,
+    reentrant_limit: usize,
+    reentrant_reclaim_cap: usize,
 }
 
 /********** impl Default **************************************************************************/
@@ -38,6 +52,70 @@ impl Config {
         Self {
             check_threshold: DEFAULT_CHECK_THRESHOLD,
             advance_threshold: DEFAULT_ADVANCE_THRESHOLD,
+            eager_rotation: true,
+            guard_lifetime_warn: 0,
+            active_retire_warn: DEFAULT_ACTIVE_RETIRE_WARN,
+            min_abandon_records: 0,
+            adaptive: false,
+            reclaim_byte_budget: 0,
+            quiescent_advance: false,
+            global_memory_budget: 0,
+            max_buffered_records: 0,
+            reentrant_limit: 0,
+            reentrant_reclaim_cap: 0,
+        }
+    }
+
+    /// Creates a new [`Config`] balancing throughput, latency and memory usage,
+    /// which is equivalent to the default configuration.
+    #[inline]
+    pub const fn balanced() -> Self {
+        Self::new()
+    }
+
+    /// Creates a new [`Config`] optimized for a low latency of individual
+    /// operations.
+    ///
+    /// Creating a guard never reclaims any records, since the rotation of the
+    /// epoch bags is deferred until the next record is retired, and the
+    /// thresholds are adjusted at runtime to avoid contention when advancing
+    /// the global epoch.
+    #[inline]
+    pub const fn low_latency() -> Self {
+        Self { eager_rotation: false, adaptive: true, ..Self::new() }
+    }
+
+    /// Creates a new [`Config`] optimized for a high throughput of operations.
+    ///
+    /// Threads check and attempt to advance the global epoch only rarely,
+    /// which reduces the synchronization overhead at the cost of more records
+    /// pending reclamation.
+    #[inline]
+    pub const fn high_throughput() -> Self {
+        Self {
+            check_threshold: 10 * DEFAULT_CHECK_THRESHOLD,
+            advance_threshold: 10 * DEFAULT_ADVANCE_THRESHOLD,
+            ..Self::new()
+        }
+    }
+
+    /// Creates a new [`Config`] optimized for a low number of records pending
+    /// reclamation.
+    ///
+    /// Threads check and attempt to advance the global epoch frequently,
+    /// lower their thresholds further as their records accumulate and exiting
+    /// threads with only few pending records reclaim them in place instead of
+    /// abandoning them.
+    /// The bag size, which also affects the memory usage, can only be chosen at
+    /// compile time through the `bag-size-*` features.
+    #[inline]
+    pub const fn low_memory() -> Self {
+        Self {
+            check_threshold: DEFAULT_CHECK_THRESHOLD / 10,
+            advance_threshold: DEFAULT_ADVANCE_THRESHOLD / 10,
+            min_abandon_records: 64,
+            adaptive: true,
+            ..Self::new()
         }
     }
 
@@ -45,7 +123,7 @@ impl Config {
     #[inline]
     pub fn with_params(check_threshold: u32, advance_threshold: u32) -> Self {
         assert!(check_threshold > 0, "the check threshold must be larger than 0");
-        Self { check_threshold, advance_threshold }
+        Self { check_threshold, advance_threshold, ..Self::new() }
     }
 
     #[inline]
@@ -59,6 +137,86 @@ impl Config {
     pub fn advance_threshold(self) -> u32 {
         self.advance_threshold
     }
+
+    /// Returns `true` if epoch bags are rotated eagerly, i.e. as soon as a
+    /// thread observes an advanced global epoch.
+    #[inline]
+    pub fn eager_rotation(self) -> bool {
+        self.eager_rotation
+    }
+
+    /// Returns the number of operations after which a live guard is reported
+    /// as likely leaked, or 0, if the guard lifetime watchdog is disabled.
+    #[inline]
+    pub fn guard_lifetime_warn(self) -> u32 {
+        self.guard_lifetime_warn
+    }
+
+    /// Returns the number of records a thread may retire while continuously
+    /// active, before a warning is emitted, or 0, if the warning is disabled.
+    #[inline]
+    pub fn active_retire_warn(self) -> u32 {
+        self.active_retire_warn
+    }
+
+    /// Returns the number of pending records below which an exiting thread
+    /// attempts to reclaim its records in place before abandoning the rest.
+    #[inline]
+    pub fn min_abandon_records(self) -> u32 {
+        self.min_abandon_records
+    }
+
+    /// Returns `true` if the check and advance thresholds are adjusted by
+    /// each thread at runtime.
+    #[inline]
+    pub fn adaptive(self) -> bool {
+        self.adaptive
+    }
+
+    /// Returns the number of pending bytes per thread, above which a thread
+    /// forces an attempt to reclaim its records, or 0, if no budget is set.
+    #[inline]
+    pub fn reclaim_byte_budget(self) -> usize {
+        self.reclaim_byte_budget
+    }
+
+    /// Returns `true` if the last active thread becoming inactive eagerly
+    /// advances the global epoch and reclaims its records.
+    #[inline]
+    pub fn quiescent_advance(self) -> bool {
+        self.quiescent_advance
+    }
+
+    /// Returns the number of pending bytes of all threads combined, which a
+    /// retiring thread attempts not to exceed, or 0, if no budget is set.
+    #[inline]
+    pub fn global_memory_budget(self) -> usize {
+        self.global_memory_budget
+    }
+
+    /// Returns the number of pending records per thread, at or above which a
+    /// thread forces an attempt to reclaim its records, or 0, if no limit is
+    /// set.
+    #[inline]
+    pub fn max_buffered_records(self) -> usize {
+        self.max_buffered_records
+    }
+
+    /// Returns the number of re-entrantly retired records, which a thread
+    /// retires at most after a single reclamation pass, or 0, if no limit is
+    /// set.
+    #[inline]
+    pub fn reentrant_limit(self) -> usize {
+        self.reentrant_limit
+    }
+
+    /// Returns the number of records in the current epoch bag queue, at or
+    /// above which re-entrantly retired records are deferred until the next
+    /// rotation, or 0, if no cap is set.
+    #[inline]
+    pub fn reentrant_reclaim_cap(self) -> usize {
+        self.reentrant_reclaim_cap
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -70,6 +228,17 @@ impl Config {
 pub struct ConfigBuilder {
     check_threshold: Option<u32>,
     advance_threshold: Option<u32>,
+    eager_rotation: Option<bool>,
+    guard_lifetime_warn: Option<u32>,
+    active_retire_warn: Option<u32>,
+    min_abandon_records: Option<u32>,
+    adaptive: Option<bool>,
+    reclaim_byte_budget: Option<usize>,
+    quiescent_advance: Option<bool>,
+    global_memory_budget: Option<usize>,
+    max_buffered_records: Option<usize>,
+    reentrant_limit: Option<usize>,
+    reentrant_reclaim_cap: Option<usize>,
 }
 
 /********** impl inherent *************************************************************************/
@@ -95,13 +264,260 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets whether epoch bags are rotated eagerly (default) or lazily.
+    ///
+    /// With eager rotation, a thread reclaims its oldest retired records as
+    /// soon as it observes an advanced global epoch when creating a new guard.
+    /// With lazy rotation, guard creation never reclaims any records and the
+    /// rotation is deferred until the thread either retires its next record
+    /// or explicitly calls `try_flush`.
+    #[inline]
+    pub fn eager_rotation(mut self, eager_rotation: bool) -> Self {
+        self.eager_rotation = Some(eager_rotation);
+        self
+    }
+
+    /// Sets the number of operations (nested guard creations and retired
+    /// records) a thread may perform while holding the same outermost guard,
+    /// before a warning about a likely leaked guard is emitted.
+    ///
+    /// Setting this to 0 (default) disables the watchdog.
+    /// The watchdog is only compiled in debug builds and has no effect in
+    /// release builds.
+    #[inline]
+    pub fn guard_lifetime_warn(mut self, guard_lifetime_warn: u32) -> Self {
+        self.guard_lifetime_warn = Some(guard_lifetime_warn);
+        self
+    }
+
+    /// Sets the number of records a thread may retire while it is continuously
+    /// active (i.e. while holding the same outermost guard), before a warning
+    /// is emitted (default: 100_000).
+    ///
+    /// A thread retiring records without ever becoming inactive can neither
+    /// reclaim its own records nor let any other thread advance the global
+    /// epoch, which is a common mistake.
+    /// Setting this to 0 disables the warning.
+    /// The warning is only compiled in debug builds and has no effect in
+    /// release builds.
+    #[inline]
+    pub fn active_retire_warn(mut self, active_retire_warn: u32) -> Self {
+        self.active_retire_warn = Some(active_retire_warn);
+        self
+    }
+
+    /// Sets the number of pending records below which an exiting thread
+    /// reclaims all of its records in place, which are safe to reclaim
+    /// according to the current global epoch, instead of abandoning them.
+    ///
+    /// Any remaining records are abandoned as usual.
+    /// Setting this to 0 (default) disables reclamation on exit.
+    #[inline]
+    pub fn min_abandon_records(mut self, min_abandon_records: u32) -> Self {
+        self.min_abandon_records = Some(min_abandon_records);
+        self
+    }
+
+    /// Sets whether the check and advance thresholds are adjusted by each
+    /// thread at runtime (default: `false`).
+    ///
+    /// In adaptive mode, the configured thresholds only serve as starting
+    /// values:
+    /// Threads lower their thresholds when their retired records accumulate,
+    /// in order to reclaim them sooner, and raise them again when their
+    /// attempts to advance the global epoch fail due to contention.
+    #[inline]
+    pub fn adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
+    /// Sets the number of bytes of retired but not yet reclaimed records per
+    /// thread, above which each further retirement forces a scan of all
+    /// threads and an attempt to advance the global epoch and reclaim the
+    /// oldest records right away.
+    ///
+    /// This bounds the memory held by each thread rather than the number of
+    /// operations between reclamation attempts, but each forced scan visits
+    /// all threads at once.
+    /// The size of a record is the size of its value type, so memory owned
+    /// by the value (e.g. the contents of a `Vec`) is not accounted for.
+    /// Setting this to 0 (default) disables the budget.
+    #[inline]
+    pub fn reclaim_byte_budget(mut self, reclaim_byte_budget: usize) -> Self {
+        self.reclaim_byte_budget = Some(reclaim_byte_budget);
+        self
+    }
+
+    /// Sets whether a thread, which becomes inactive as the last active thread,
+    /// eagerly advances the global epoch and reclaims its records (default:
+    /// `false`).
+    ///
+    /// When all threads are quiescent, the global epoch can be advanced right
+    /// away, which speeds up reclamation in bursty workloads with idle gaps.
+    /// However, threads then have to count themselves in a global counter
+    /// whenever they become active or inactive, which adds contention.
+    #[inline]
+    pub fn quiescent_advance(mut self, quiescent_advance: bool) -> Self {
+        self.quiescent_advance = Some(quiescent_advance);
+        self
+    }
+
+    /// Sets the number of bytes of retired but not yet reclaimed records of
+    /// all threads combined, which a retirement must not push the global total
+    /// above.
+    ///
+    /// Before retiring a record that would exceed the budget, the retiring
+    /// thread synchronously scans all threads, advances the global epoch and
+    /// reclaims its own records until the record fits.
+    /// This trades latency for memory: a retiring thread may have to wait for
+    /// several full grace periods, each of which requires all active threads
+    /// to observe the current global epoch, so retirements can become as slow
+    /// as the slowest concurrent guard.
+    /// Since threads can only reclaim their own records, a thread, which can
+    /// not free enough of its own records (e.g. because it retires while
+    /// holding a guard or because the budget is held by other threads), gives
+    /// up after a bounded number of attempts and exceeds the budget.
+    /// Records of exited threads are no longer accounted for once they are
+    /// abandoned.
+    /// Setting this to 0 (default) disables the budget.
+    #[inline]
+    pub fn global_memory_budget(mut self, global_memory_budget: usize) -> Self {
+        self.global_memory_budget = Some(global_memory_budget);
+        self
+    }
+
+    /// Sets the number of retired but not yet reclaimed records per thread, at
+    /// or above which each further retirement forces a scan of all threads and
+    /// an attempt to advance the global epoch and reclaim the oldest records
+    /// right away, instead of waiting for the check and advance thresholds.
+    ///
+    /// This trades throughput for a ceiling on the memory held by a thread
+    /// retiring heavily while the global epoch advances slowly, since every
+    /// retirement above the limit visits all threads at once.
+    /// Records are still only reclaimed after the global epoch has advanced
+    /// twice since their retirement, so the limit can be exceeded while other
+    /// threads remain active in older epochs.
+    /// Adopted abandoned bags are counted as a single record each.
+    /// Setting this to 0 (default) disables the limit.
+    #[inline]
+    pub fn max_buffered_records(mut self, max_buffered_records: usize) -> Self {
+        self.max_buffered_records = Some(max_buffered_records);
+        self
+    }
+
+    /// Sets the number of records retired re-entrantly (i.e. by the
+    /// destructors of records reclaimed by the same thread), which are retired
+    /// at most after a single reclamation pass.
+    ///
+    /// Re-entrantly retired records are always collected in a per-thread
+    /// worklist and retired iteratively once the pass is complete, since
+    /// retiring them recursively could exhaust the stack for deeply nested
+    /// structures.
+    /// Any records above the limit remain in the worklist until the thread
+    /// next accesses its local state (e.g. when creating a guard), which
+    /// bounds the work done by a single guard for recursive structures.
+    /// Records remaining in the worklist are retired in full when the thread
+    /// exits.
+    /// Setting this to 0 (default) disables the limit.
+    #[inline]
+    pub fn reentrant_limit(mut self, reentrant_limit: usize) -> Self {
+        self.reentrant_limit = Some(reentrant_limit);
+        self
+    }
+
+    /// Sets the number of records in the current epoch bag queue, at or above
+    /// which no further re-entrantly retired records are retired into it.
+    ///
+    /// Since each reclamation pass reclaims one entire bag queue, this caps
+    /// the number of re-entrantly retired records reclaimed in a single pass,
+    /// deferring the remaining ones in the worklist (see
+    /// [`reentrant_limit`][ConfigBuilder::reentrant_limit]) until the queues
+    /// have been rotated.
+    /// Records retired regularly are not affected by the cap.
+    /// Setting this to 0 (default) disables the cap.
+    #[inline]
+    pub fn reentrant_reclaim_cap(mut self, reentrant_reclaim_cap: usize) -> Self {
+        self.reentrant_reclaim_cap = Some(reentrant_reclaim_cap);
+        self
+    }
+
     /// Consumes the builder and creates a new [`Config`] instance with the
     /// configured parameters or their default values, if they were not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the configured parameters are invalid (see
+    /// [`try_build`][ConfigBuilder::try_build]).
     #[inline]
     pub fn build(self) -> Config {
-        Config::with_params(
-            self.check_threshold.unwrap_or(DEFAULT_CHECK_THRESHOLD),
+        match self.try_build() {
+            Ok(config) => config,
+            Err(err) => panic!("invalid configuration: {}", err),
+        }
+    }
+
+    /// Consumes the builder and creates a new [`Config`] instance with the
+    /// configured parameters or their default values, if they were not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if any of the configured parameters are invalid, e.g.
+    /// when they have been read from a configuration file.
+    #[inline]
+    pub fn try_build(self) -> Result<Config, ConfigError> {
+        let check_threshold = self.check_threshold.unwrap_or(DEFAULT_CHECK_THRESHOLD);
+        if check_threshold == 0 {
+            return Err(ConfigError::ZeroCheckThreshold);
+        }
+
+        let config = Config::with_params(
+            check_threshold,
             self.advance_threshold.unwrap_or(DEFAULT_ADVANCE_THRESHOLD),
-        )
+        );
+
+        Ok(Config {
+            eager_rotation: self.eager_rotation.unwrap_or(true),
+            guard_lifetime_warn: self.guard_lifetime_warn.unwrap_or(0),
+            active_retire_warn: self.active_retire_warn.unwrap_or(DEFAULT_ACTIVE_RETIRE_WARN),
+            min_abandon_records: self.min_abandon_records.unwrap_or(0),
+            adaptive: self.adaptive.unwrap_or(false),
+            reclaim_byte_budget: self.reclaim_byte_budget.unwrap_or(0),
+            quiescent_advance: self.quiescent_advance.unwrap_or(false),
+            global_memory_budget: self.global_memory_budget.unwrap_or(0),
+            max_buffered_records: self.max_buffered_records.unwrap_or(0),
+            reentrant_limit: self.reentrant_limit.unwrap_or(0),
+            reentrant_reclaim_cap: self.reentrant_reclaim_cap.unwrap_or(0),
+            ..config
+        })
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// ConfigError
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// An error for invalid parameters of a [`ConfigBuilder`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ConfigError {
+    /// The check threshold has been set to 0.
+    ZeroCheckThreshold,
+}
+
+/********** impl Display **************************************************************************/
+
+impl fmt::Display for ConfigError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::ZeroCheckThreshold => {
+                write!(f, "the check threshold must be larger than 0")
+            }
+        }
+    }
+}
+
+/********** impl Error ****************************************************************************/
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,19 @@
+//! Synchronous reclamation for debugging, which is only compiled with the
+//! `debug-sync` feature.
+
+use core::sync::atomic::{AtomicBool, Ordering::Relaxed};
+
+/// The flag determining whether retired records are reclaimed immediately.
+static SYNCHRONOUS: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether retired records are reclaimed immediately.
+#[inline]
+pub(crate) fn set_synchronous_reclaim(synchronous: bool) {
+    SYNCHRONOUS.store(synchronous, Relaxed);
+}
+
+/// Returns `true` if retired records are reclaimed immediately.
+#[inline]
+pub(crate) fn is_synchronous() -> bool {
+    SYNCHRONOUS.load(Relaxed)
+}
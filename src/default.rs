@@ -1,18 +1,45 @@
 //! Thread local variables and access abstractions for *std* environments.
 
+use std::alloc::{self, Layout};
 use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+use std::sync::atomic::Ordering::{self, SeqCst};
+use std::thread;
+use std::time::Duration;
+#[cfg(feature = "testing")]
+use std::time::Instant;
 
 use debra_common::reclaim;
 use debra_common::LocalAccess;
-use reclaim::{GlobalReclaim, Reclaim};
+use reclaim::prelude::*;
+use reclaim::{GlobalReclaim, MarkedPtr, Reclaim};
 
+use crate::deferred::Deferred;
+use crate::global::{epoch_number, EPOCH};
 use crate::guard::Guard;
 use crate::local::Local;
+#[cfg(feature = "testing")]
+use crate::testing::PendingRecord;
 use crate::typenum::Unsigned;
-use crate::{Debra, Retired, Unlinked};
+use crate::{Atomic, Debra, Retired, RetiredExt, Shared, Unlinked};
 
 thread_local!(static LOCAL: Local = Local::new());
 
+/// Retires the given `record` of `size` bytes in the current thread's local
+/// state.
+///
+/// If the thread local state is already being destroyed (e.g. because a record
+/// reclaimed by the exiting thread retires further records when it is
+/// dropped), the record is abandoned instead.
+#[inline]
+pub(crate) fn retire_sized(record: Retired, size: usize) {
+    let mut record = Some(record);
+    if LOCAL.try_with(|local| local.retire_sized(record.take().unwrap(), size)).is_err() {
+        crate::abandon_record(record.take().unwrap());
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // Debra
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -26,6 +53,438 @@ impl Debra {
     pub fn is_thread_active() -> bool {
         LOCAL.with(|local| local.is_active())
     }
+
+    /// Creates a new [`Guard`] and passes it to `f`, so that a sequence of
+    /// operations (e.g. several pops from the same stack) is performed under a
+    /// single guard.
+    ///
+    /// Holding one guard across several operations is always correct, since a
+    /// guard protects all values loaded during its entire lifetime, and it is
+    /// considerably cheaper than creating a separate guard for each operation
+    /// (see the `pin` benchmarks), as only the outermost guard announces the
+    /// thread as active.
+    /// However, no records retired by any thread can be reclaimed while the
+    /// guard is alive, so `f` should not block or run for a long time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering::Acquire;
+    ///
+    /// use debra::Debra;
+    ///
+    /// type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+    ///
+    /// let values: Vec<Atomic<i32>> = (0..4).map(Atomic::new).collect();
+    /// let sum: i32 = Debra::pin_batch(|guard| {
+    ///     values.iter().map(|atomic| *atomic.load(Acquire, guard).unwrap()).sum()
+    /// });
+    ///
+    /// assert_eq!(sum, 6);
+    /// ```
+    #[inline]
+    pub fn pin_batch<R>(f: impl FnOnce(&Guard<DefaultAccess>) -> R) -> R {
+        f(&Guard::new())
+    }
+
+    /// Creates a new [`Guard`] and loads the value of `atomic` using the given
+    /// memory `order`, packaging the common "pin, then load one pointer" idiom
+    /// into a single call.
+    ///
+    /// The loaded value can only be accessed through the returned [`Pinned`],
+    /// so that it can not outlive the guard protecting it.
+    ///
+    /// # Examples
+    ///
+    /// A simplified `pop` of a Treiber stack:
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+    ///
+    /// use debra::reclaim::prelude::*;
+    /// use debra::Debra;
+    ///
+    /// type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+    ///
+    /// struct Node {
+    ///     elem: i32,
+    ///     next: Atomic<Node>,
+    /// }
+    ///
+    /// let head = Atomic::new(Node { elem: 1, next: Atomic::null() });
+    ///
+    /// let pinned = Debra::pin_and_load(&head, Acquire);
+    /// if let Marked::Value(node) = pinned.shared() {
+    ///     let next = node.next.load_unprotected(Relaxed);
+    ///     if let Ok(unlinked) = head.compare_exchange(node, next, Release, Relaxed) {
+    ///         assert_eq!(unlinked.elem, 1);
+    ///         unsafe { unlinked.retire() };
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn pin_and_load<T, N: Unsigned>(atomic: &Atomic<T, N>, order: Ordering) -> Pinned<T, N> {
+        let guard = Guard::new();
+        let ptr = atomic.load_raw(order);
+        Pinned { guard, ptr }
+    }
+
+    /// Retires the given `unlinked` record and invokes `notify` right after the
+    /// record has been reclaimed (i.e. dropped and de-allocated).
+    ///
+    /// `notify` is called on whichever thread eventually reclaims the record.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for
+    /// [`retire`][reclaim::GlobalReclaim::retire] apply.
+    #[inline]
+    pub unsafe fn retire_with_notify<T: 'static, N: Unsigned>(
+        unlinked: Unlinked<T, N>,
+        notify: impl FnOnce() + Send + 'static,
+    ) {
+        let unmarked = unlinked.into_marked_non_null().decompose_non_null();
+        let mut record = Retired::new_unchecked(unmarked);
+        let deferred = Deferred::new(move || {
+            record.reclaim();
+            notify();
+        });
+
+        let retired = crate::retired::typed_box::<T, _>(Box::new(deferred));
+        retire_sized(retired, mem::size_of::<T>());
+    }
+
+    /// Retires all given `unlinked` records at once, e.g. after unlinking many
+    /// nodes of a data structure together.
+    ///
+    /// The thread local state is only accessed once for the entire batch, so
+    /// this is cheaper than retiring each record separately.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for
+    /// [`retire`][reclaim::GlobalReclaim::retire] apply to each record.
+    #[inline]
+    pub unsafe fn retire_batch<T: 'static, N: Unsigned>(
+        unlinked: impl IntoIterator<Item = Unlinked<T, N>>,
+    ) {
+        let records = unlinked
+            .into_iter()
+            .map(|unlinked| (crate::retired_from_unlinked(unlinked), mem::size_of::<T>()));
+
+        let mut records = Some(records);
+        if LOCAL.try_with(|local| local.retire_sized_batch(records.take().unwrap())).is_err() {
+            records.take().unwrap().for_each(|(record, _)| crate::abandon_record(record));
+        }
+    }
+
+    /// Retires the given plain `boxed` value, which has not been allocated
+    /// through an [`Owned`][crate::Owned] (e.g. by an existing data structure
+    /// using raw pointers obtained with [`Box::into_raw`]), deferring its drop
+    /// and de-allocation until all threads, which are currently active, have
+    /// become inactive at least once.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for
+    /// [`retire`][reclaim::GlobalReclaim::retire] apply, i.e. the value must
+    /// no longer be reachable for any thread, although it may still be
+    /// accessed by threads having obtained a reference to it earlier.
+    #[inline]
+    pub unsafe fn retire_box<T: 'static>(boxed: Box<T>) {
+        let retired = crate::retired::typed_box::<T, _>(boxed);
+        retire_sized(retired, mem::size_of::<T>());
+    }
+
+    /// Retires the given `boxed` value of a dynamically sized type (e.g. a
+    /// `Box<dyn Trait>`), deferring its drop and de-allocation until all
+    /// threads, which are currently active, have become inactive at least
+    /// once.
+    ///
+    /// Since records can only be retired through thin pointers, the fat
+    /// `boxed` pointer (including its vtable or length) is itself boxed, which
+    /// requires an additional allocation.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for [`retire_box`][Debra::retire_box]
+    /// apply.
+    #[inline]
+    pub unsafe fn retire_dyn<T: ?Sized + 'static>(boxed: Box<T>) {
+        let size = mem::size_of_val(&*boxed);
+        let retired = crate::retired::typed_box::<T, _>(Box::new(boxed));
+        retire_sized(retired, size);
+    }
+
+    /// Retires the manually allocated memory at `ptr`, deferring the invocation
+    /// of the optional `drop` function and the subsequent de-allocation with
+    /// the given `layout` until all threads, which are currently active, have
+    /// become inactive at least once.
+    ///
+    /// This allows using DEBRA for memory, which has not been allocated as a
+    /// `Box` (e.g. with [`alloc::alloc`][std::alloc::alloc] and an explicit
+    /// [`Layout`]), where reclaiming it as a `Box` would be unsound.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for
+    /// [`retire`][reclaim::GlobalReclaim::retire] apply.
+    /// Additionally, `ptr` must have been allocated by the global allocator
+    /// with exactly the given `layout` and `drop` must be safe to call with
+    /// `ptr` from any thread.
+    #[inline]
+    pub unsafe fn retire_with_layout(
+        ptr: NonNull<u8>,
+        layout: Layout,
+        drop: Option<unsafe fn(NonNull<u8>)>,
+    ) {
+        let deferred = Deferred::new(move || {
+            if let Some(drop) = drop {
+                drop(ptr);
+            }
+
+            alloc::dealloc(ptr.as_ptr(), layout);
+        });
+
+        let retired = Retired::from_box(Box::new(deferred));
+        retire_sized(retired, layout.size());
+    }
+
+    /// Temporarily removes the current thread from the scans of all other
+    /// threads, so that it can no longer prevent the global epoch from being
+    /// advanced, e.g. during a long computation that does not access any
+    /// shared data.
+    ///
+    /// The thread is announced as inactive, even if it currently has live
+    /// [`Guard`]s, and remains so until [`rejoin_scan`][Debra::rejoin_scan] is
+    /// called or a new outermost [`Guard`] is created.
+    ///
+    /// # Safety
+    ///
+    /// No references obtained through any of the current thread's live
+    /// [`Guard`]s must be dereferenced until
+    /// [`rejoin_scan`][Debra::rejoin_scan] is called.
+    #[inline]
+    pub unsafe fn leave_scan() {
+        LOCAL.with(|local| local.leave_scan());
+    }
+
+    /// Re-includes the current thread in the scans of all other threads after a
+    /// previous call to [`leave_scan`][Debra::leave_scan].
+    #[inline]
+    pub fn rejoin_scan() {
+        LOCAL.with(|local| local.rejoin_scan());
+    }
+
+    /// Returns the number of records currently cached in each of the current
+    /// thread's epoch bag queues, starting with the queue of the current epoch.
+    #[inline]
+    pub fn bag_balance() -> [usize; 3] {
+        LOCAL.with(|local| local.bag_balance())
+    }
+
+    /// Returns the total size in bytes of all records currently cached in the
+    /// current thread's epoch bag queues.
+    ///
+    /// See [`ConfigBuilder::reclaim_byte_budget`][crate::ConfigBuilder::reclaim_byte_budget]
+    /// for bounding this value.
+    #[inline]
+    pub fn pending_bytes() -> usize {
+        LOCAL.with(|local| local.pending_bytes())
+    }
+
+    /// Returns the index of the current thread's epoch bag queue, in which
+    /// records are currently retired.
+    ///
+    /// This is only intended for white-box testing of the rotation logic.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn current_bag_index() -> usize {
+        LOCAL.with(|local| local.current_bag_index())
+    }
+
+    /// Returns the number of rotations of the current thread's epoch bag
+    /// queues, which have been performed so far.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn rotations_performed() -> u64 {
+        LOCAL.with(|local| local.rotations_performed())
+    }
+
+    /// Returns the epoch and the number of records of each of the current
+    /// thread's epoch bag queues, starting with the queue of the current epoch.
+    #[inline]
+    pub fn pending_by_epoch() -> [(u64, usize); 3] {
+        LOCAL.with(|local| local.pending_by_epoch())
+    }
+
+    /// Reclaims the records retired by the current thread in the given
+    /// `epoch` and returns their number.
+    ///
+    /// The records are only reclaimed once the global epoch has reached
+    /// `epoch + 3`, otherwise 0 is returned.
+    /// See [`pending_by_epoch`][Debra::pending_by_epoch] for the epochs of the
+    /// current thread's pending records.
+    #[inline]
+    pub fn reclaim_epoch(epoch: u64) -> usize {
+        LOCAL.with(|local| local.reclaim_epoch(epoch))
+    }
+
+    /// Returns `true` if the current thread's largest epoch bag queue contains
+    /// more than `ratio` times the average number of records per queue.
+    #[inline]
+    pub fn is_bag_imbalanced(ratio: f64) -> bool {
+        LOCAL.with(|local| local.is_imbalanced(ratio))
+    }
+
+    /// Marks the end of a batch of operations (e.g. a logical transaction) of
+    /// the current thread, so that reclamation is checkpointed at the
+    /// application's own logical units instead of only by counting guards.
+    ///
+    /// The next incremental check for advancing the global epoch is performed
+    /// immediately, if the thread is currently active, or otherwise when the
+    /// next outermost [`Guard`] is created, regardless of the configured
+    /// [check threshold][crate::ConfigBuilder::check_threshold].
+    #[inline]
+    pub fn checkpoint() {
+        LOCAL.with(|local| local.checkpoint());
+    }
+
+    /// Attempts to advance the global epoch once and returns `true` if it has
+    /// been advanced by this call.
+    ///
+    /// This drives the same incremental checks, which are otherwise performed
+    /// one thread at a time when creating [`Guard`]s, to completion at once,
+    /// so the epoch can be driven forward deterministically, e.g. in
+    /// benchmarks or test harnesses.
+    /// The epoch is never advanced prematurely: If any other thread is active
+    /// and has not yet announced the current global epoch, or if the current
+    /// thread is itself active, this returns `false` without any effect.
+    #[inline]
+    pub fn try_advance_epoch() -> bool {
+        LOCAL.with(|local| local.try_advance_epoch())
+    }
+
+    /// Blocks the current thread until a full grace period has elapsed, i.e.
+    /// until every thread, which was active when this function was called, has
+    /// become inactive at least once.
+    ///
+    /// This is the equivalent of the classic RCU `synchronize_rcu` primitive
+    /// and allows, for instance, to safely re-use a buffer that was previously
+    /// handed off to readers, without ever retiring it.
+    /// The grace period has elapsed once the global epoch has been advanced
+    /// twice, which the calling thread actively attempts while waiting.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the current thread is active (i.e. has a live [`Guard`]),
+    /// since it would then prevent the global epoch from being advanced and
+    /// hence wait forever.
+    ///
+    /// # Deadlocks
+    ///
+    /// The call blocks for as long as any other thread remains active, e.g.
+    /// while it itself waits for the calling thread, which results in a
+    /// deadlock.
+    pub fn synchronize_rcu() {
+        assert!(!Self::is_thread_active(), "`synchronize_rcu` called with a live guard");
+
+        LOCAL.with(|local| {
+            let start = epoch_number(EPOCH.load(SeqCst));
+            while epoch_number(EPOCH.load(SeqCst)).wrapping_sub(start) < 2 {
+                // the attempt fails, if another thread still lags behind (or has concurrently
+                // advanced the epoch itself), so only then it is worth giving up the time slice
+                if !local.try_advance_epoch() {
+                    thread::yield_now();
+                }
+            }
+        });
+    }
+
+    /// Unconditionally reclaims all records retired by the calling thread and
+    /// all abandoned records of exited threads, regardless of the global epoch,
+    /// e.g. for a clean shutdown after all worker threads have been joined.
+    ///
+    /// Records retired by other threads, which are still running, are not
+    /// reclaimed.
+    ///
+    /// # Safety
+    ///
+    /// This bypasses all epoch checks, so the calling thread must not be
+    /// active and no other thread must be active or still be able to access
+    /// any retired record.
+    #[inline]
+    pub unsafe fn reclaim_all_now() {
+        LOCAL.with(|local| local.reclaim_all_now());
+    }
+
+    /// Returns the estimated time until a record retired now will be
+    /// reclaimed.
+    ///
+    /// Retired records are reclaimed after the global epoch has been advanced
+    /// twice, so the estimate is based on the (moving) average time between
+    /// two epoch advances.
+    /// Returns `None`, if the epoch has not yet been advanced often enough for
+    /// a meaningful estimate.
+    #[inline]
+    pub fn estimated_reclaim_delay() -> Option<Duration> {
+        crate::time::estimated_reclaim_delay()
+    }
+
+    /// Overrides the time source used by all timing features (e.g.
+    /// [`estimated_reclaim_delay`][Debra::estimated_reclaim_delay]) with the
+    /// given `clock`.
+    ///
+    /// This is intended for deterministically testing time-dependent behaviour
+    /// with a fake clock, which is only advanced manually.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn set_clock(clock: impl Fn() -> Instant + Send + Sync + 'static) {
+        crate::time::set_clock(clock);
+    }
+
+    /// Returns a snapshot of all records retired by any thread, which have not
+    /// yet been reclaimed, including those in the abandoned bags of exited
+    /// threads, sorted by their retire epochs.
+    ///
+    /// This is intended for diagnosing leaks, e.g. in a post-mortem dump.
+    /// Since all threads retire and reclaim records concurrently, records may
+    /// be reclaimed or retired right after the snapshot is taken.
+    /// Only records retired while the `testing` feature is enabled are
+    /// registered, which excludes the final records of exiting threads.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn dump_pending() -> Vec<PendingRecord> {
+        crate::sanitizer::pending_records()
+    }
+
+    /// Sets the `executor`, to which the reclamation (i.e. dropping and
+    /// de-allocating) of all subsequently retired records is offloaded, e.g.
+    /// for running expensive destructors in parallel on a thread pool.
+    ///
+    /// The executor is called with one job per record, once the record has
+    /// become safe to reclaim, and must eventually run each job exactly once.
+    /// Jobs may be run on any thread and in any order, so the usual
+    /// [reclamation order][Debra#reclamation-order] no longer holds.
+    /// Retiring each record requires an additional allocation while an
+    /// executor is set.
+    #[inline]
+    pub fn set_reclaim_executor(
+        executor: impl Fn(Box<dyn FnOnce() + Send>) + Send + Sync + 'static,
+    ) {
+        crate::executor::set_reclaim_executor(executor);
+    }
+
+    /// Returns `true` if the global epoch has been advanced at least once
+    /// within the given `stale_after` duration.
+    ///
+    /// This is a cheap liveness probe for the reclamation scheme:
+    /// If it returns `false`, reclamation is likely stalled by some thread,
+    /// which remains active indefinitely (e.g. due to a leaked [`Guard`]).
+    #[inline]
+    pub fn reclamation_healthy(stale_after: Duration) -> bool {
+        crate::time::since_last_advance() <= stale_after
+    }
 }
 
 /***** impl GlobalReclaim *************************************************************************/
@@ -40,12 +499,12 @@ unsafe impl GlobalReclaim for Debra {
 
     #[inline]
     unsafe fn retire<T: 'static, N: Unsigned>(unlinked: Unlinked<T, N>) {
-        LOCAL.with(move |local| Self::retire_local(local, unlinked));
+        Self::retire_unchecked(unlinked);
     }
 
     #[inline]
     unsafe fn retire_unchecked<T, N: Unsigned>(unlinked: Unlinked<T, N>) {
-        LOCAL.with(move |local| Self::retire_local_unchecked(local, unlinked));
+        retire_sized(crate::retired_from_unlinked(unlinked), mem::size_of::<T>());
     }
 }
 
@@ -60,6 +519,49 @@ impl Guard<DefaultAccess> {
     pub fn new() -> Self {
         Self::with_local_access(DefaultAccess::default())
     }
+
+    /// Returns the nesting depth of the thread's live guards, including this
+    /// one, i.e. 1 for a single outermost guard.
+    #[inline]
+    pub fn pin_count(&self) -> usize {
+        LOCAL.with(|local| local.guard_count())
+    }
+
+    /// Returns `true` if this is the thread's only live guard, i.e. the
+    /// outermost guard without any nested guards or clones.
+    #[inline]
+    pub fn is_outermost(&self) -> bool {
+        self.pin_count() == 1
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Pinned
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A [`Guard`] together with a value, which has been loaded while the guard
+/// was alive (see [`Debra::pin_and_load`]).
+pub struct Pinned<T, N: Unsigned> {
+    guard: Guard<DefaultAccess>,
+    ptr: MarkedPtr<T, N>,
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl<T, N: Unsigned> Pinned<T, N> {
+    /// Returns the loaded value, which is protected for as long as `self` is
+    /// alive.
+    #[inline]
+    pub fn shared(&self) -> Marked<Shared<T, N>> {
+        unsafe { Marked::from_marked_ptr(self.ptr) }
+    }
+
+    /// Returns a reference to the [`Guard`] protecting the loaded value, e.g.
+    /// for loading further values.
+    #[inline]
+    pub fn guard(&self) -> &Guard<DefaultAccess> {
+        &self.guard
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -96,6 +598,6 @@ impl LocalAccess for DefaultAccess {
 
     #[inline]
     fn retire_record(self, record: Retired) {
-        LOCAL.with(move |local| local.retire_record(record));
+        retire_sized(record, 0);
     }
 }
@@ -0,0 +1,37 @@
+//! Records, which run an arbitrary closure instead of (only) dropping a value
+//! when they are reclaimed.
+
+use core::mem::ManuallyDrop;
+use core::ptr;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Deferred
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A record wrapping a closure, which is invoked once the record is dropped,
+/// i.e. when it is reclaimed.
+///
+/// Since the reclamation scheme assumes an empty record header, a boxed
+/// [`Deferred`] can be retired like any other record.
+#[derive(Debug)]
+pub(crate) struct Deferred<F: FnOnce()>(ManuallyDrop<F>);
+
+/***** impl inherent ******************************************************************************/
+
+impl<F: FnOnce()> Deferred<F> {
+    /// Creates a new [`Deferred`] from the given closure `f`.
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self(ManuallyDrop::new(f))
+    }
+}
+
+/***** impl Drop **********************************************************************************/
+
+impl<F: FnOnce()> Drop for Deferred<F> {
+    #[inline]
+    fn drop(&mut self) {
+        let f = unsafe { ptr::read(&*self.0) };
+        f();
+    }
+}
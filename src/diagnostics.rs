@@ -0,0 +1,157 @@
+//! Debugging aids, most of which are only compiled in debug builds.
+
+use core::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+#[cfg(debug_assertions)]
+use crate::local::BAG_QUEUE_COUNT;
+
+/// The total number of warnings about likely leaked guards emitted so far.
+#[cfg(debug_assertions)]
+static LEAKED_GUARD_WARNINGS: AtomicUsize = AtomicUsize::new(0);
+/// The total number of warnings about records retired while continuously
+/// active emitted so far.
+#[cfg(debug_assertions)]
+static ACTIVE_RETIRE_WARNINGS: AtomicUsize = AtomicUsize::new(0);
+/// The total number of deferred closures, which have panicked when they were
+/// invoked.
+#[cfg(feature = "std")]
+static DEFERRED_PANICS: AtomicUsize = AtomicUsize::new(0);
+
+/// Emits a warning about a guard, which has been kept alive for at least `ops`
+/// operations and has therefore likely been leaked (e.g. by storing it in some
+/// long-lived struct).
+///
+/// In *std* environments, the warning is printed to `stderr`.
+#[cfg(debug_assertions)]
+#[cold]
+pub(crate) fn warn_leaked_guard(ops: u32) {
+    LEAKED_GUARD_WARNINGS.fetch_add(1, Relaxed);
+
+    #[cfg(any(test, feature = "std"))]
+    eprintln!(
+        "debra: a guard has been alive for {} operations and was likely leaked, \
+         the thread prevents the global epoch from being advanced",
+        ops
+    );
+    #[cfg(not(any(test, feature = "std")))]
+    let _ = ops;
+}
+
+/// Returns the total number of warnings about likely leaked guards emitted so
+/// far by all threads.
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn leaked_guard_warnings() -> usize {
+    LEAKED_GUARD_WARNINGS.load(Relaxed)
+}
+
+/// Emits a warning about a thread, which has retired at least `retires` records
+/// without becoming inactive once, which prevents the reclamation of all of
+/// these records.
+///
+/// In *std* environments, the warning is printed to `stderr`.
+#[cfg(debug_assertions)]
+#[cold]
+pub(crate) fn warn_active_retires(retires: u32) {
+    ACTIVE_RETIRE_WARNINGS.fetch_add(1, Relaxed);
+
+    #[cfg(any(test, feature = "std"))]
+    eprintln!(
+        "debra: a thread has retired {} records while continuously holding a guard, \
+         none of these records can be reclaimed until the thread drops all of its guards",
+        retires
+    );
+    #[cfg(not(any(test, feature = "std")))]
+    let _ = retires;
+}
+
+/// Returns the total number of warnings about records retired while
+/// continuously active emitted so far by all threads.
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn active_retire_warnings() -> usize {
+    ACTIVE_RETIRE_WARNINGS.load(Relaxed)
+}
+
+/// Emits a warning about a deferred closure, which has panicked when it was
+/// invoked by a reclaiming thread.
+///
+/// Unlike the other warnings, this is also emitted in release builds and is
+/// always printed to `stderr`.
+#[cfg(feature = "std")]
+#[cold]
+pub(crate) fn warn_deferred_panic() {
+    DEFERRED_PANICS.fetch_add(1, Relaxed);
+    eprintln!("debra: a deferred closure has panicked during reclamation");
+}
+
+/// Returns the total number of deferred closures, which have panicked so far.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn deferred_panics() -> usize {
+    DEFERRED_PANICS.load(Relaxed)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// RecentRetires
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The number of most recently retired record addresses tracked per thread.
+#[cfg(debug_assertions)]
+const RECENT_RETIRES: usize = 32;
+
+/// A ring buffer of the addresses of a thread's most recently retired records
+/// for detecting records, which are retired again while still pending.
+///
+/// Unlike the `lifecycle-check` feature, this only detects double retires
+/// among the last few records retired by the same thread.
+#[cfg(debug_assertions)]
+#[derive(Debug)]
+pub(crate) struct RecentRetires {
+    /// The addresses and the number of bag rotations at their retirement
+    entries: [(usize, u64); RECENT_RETIRES],
+    next: usize,
+    rotations: u64,
+}
+
+/***** impl inherent ******************************************************************************/
+
+#[cfg(debug_assertions)]
+impl RecentRetires {
+    /// Creates a new empty [`RecentRetires`] buffer.
+    #[inline]
+    pub fn new() -> Self {
+        Self { entries: [(0, 0); RECENT_RETIRES], next: 0, rotations: 0 }
+    }
+
+    /// Records the retirement of the record at `address`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the same address has recently been retired and the record
+    /// has not yet been reclaimed.
+    #[inline]
+    pub fn retire(&mut self, address: usize) {
+        // a record is reclaimed after its epoch bag queue has been rotated three times, after
+        // which its address may legitimately be re-used by a new record
+        let rotations = self.rotations;
+        let pending = |&(addr, retired): &(usize, u64)| {
+            addr == address && rotations - retired < BAG_QUEUE_COUNT as u64
+        };
+
+        assert!(
+            !self.entries.iter().any(pending),
+            "double retire: record at {:#x} has been retired again before being reclaimed",
+            address
+        );
+
+        self.entries[self.next] = (address, rotations);
+        self.next = (self.next + 1) % RECENT_RETIRES;
+    }
+
+    /// Records a rotation of the thread's epoch bag queues.
+    #[inline]
+    pub fn rotate(&mut self) {
+        self.rotations += 1;
+    }
+}
@@ -0,0 +1,55 @@
+//! A public snapshot of the global epoch.
+
+use core::fmt;
+use core::sync::atomic::Ordering;
+
+use crate::global::{self, EPOCH};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// EpochSnapshot
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A snapshot of the global epoch, as returned by
+/// [`Debra::current_epoch`][crate::Debra::current_epoch].
+///
+/// The epoch is given as its logical number, i.e. the number of times the
+/// global epoch had been advanced when the snapshot was taken, which matches
+/// the epochs returned by e.g. `Debra::pending_by_epoch`.
+/// Epoch numbers eventually wrap around, so snapshots should only be compared
+/// by their [`wrapping_distance`][EpochSnapshot::wrapping_distance].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EpochSnapshot(u64);
+
+/***** impl inherent ******************************************************************************/
+
+impl EpochSnapshot {
+    /// Loads the current global epoch.
+    #[inline]
+    pub(crate) fn load() -> Self {
+        Self(global::epoch_number(EPOCH.load(Ordering::Acquire)))
+    }
+
+    /// Returns the logical epoch number.
+    #[inline]
+    pub fn number(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the number of times the global epoch has been advanced between
+    /// `self` and the later snapshot `other`, accounting for wrap-around.
+    #[inline]
+    pub fn wrapping_distance(self, other: Self) -> u64 {
+        let bits = global::epoch_width_bits();
+        let mask = if bits >= 64 { u64::max_value() } else { (1 << bits) - 1 };
+        other.0.wrapping_sub(self.0) & mask
+    }
+}
+
+/***** impl Display *******************************************************************************/
+
+impl fmt::Display for EpochSnapshot {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "epoch {}", self.0)
+    }
+}
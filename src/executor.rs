@@ -0,0 +1,82 @@
+//! Offloading of the reclamation of records to a user-provided executor for
+//! *std* environments.
+
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::sync::RwLock;
+
+use conquer_once::spin::OnceCell;
+
+use crate::deferred::Deferred;
+use crate::{Retired, RetiredExt};
+
+/// The type of a job reclaiming a single record.
+pub(crate) type Job = Box<dyn FnOnce() + Send>;
+/// The type of a custom executor for reclamation jobs.
+type Executor = Box<dyn Fn(Job) + Send + Sync>;
+
+/// The custom executor, to which the reclamation of records is offloaded.
+static EXECUTOR: OnceCell<RwLock<Option<Executor>>> = OnceCell::new();
+/// The flag indicating whether an executor has been set, which avoids
+/// acquiring the lock when retiring records without one.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Sets the executor, to which the reclamation of all subsequently retired
+/// records is offloaded.
+#[inline]
+pub(crate) fn set_reclaim_executor(executor: impl Fn(Job) + Send + Sync + 'static) {
+    let lock = EXECUTOR.get_or_init(|| RwLock::new(None));
+    *lock.write().unwrap() = Some(Box::new(executor));
+    ENABLED.store(true, Relaxed);
+}
+
+/// Wraps the given `record`, so that its reclamation is offloaded to the
+/// executor, if one has been set.
+#[inline(always)]
+pub(crate) fn offloaded(record: Retired) -> Retired {
+    if ENABLED.load(Relaxed) {
+        offload(record)
+    } else {
+        record
+    }
+}
+
+/// Wraps the given `record`, so that its reclamation is offloaded to the
+/// executor.
+///
+/// # Notes
+///
+/// This is annotated with `#[cold]` to keep it out of the fast path.
+#[cold]
+fn offload(record: Retired) -> Retired {
+    let record = SendRecord(record);
+    let deferred = Deferred::new(move || {
+        let mut record = record;
+        execute(Box::new(move || unsafe { record.0.reclaim() }));
+    });
+
+    Retired::from_box(Box::new(deferred))
+}
+
+/// Passes the given `job` to the executor or runs it in place, if there is
+/// none.
+#[inline]
+fn execute(job: Job) {
+    if let Ok(lock) = EXECUTOR.try_get() {
+        if let Some(executor) = lock.read().unwrap().as_ref() {
+            return executor(job);
+        }
+    }
+
+    job();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// SendRecord
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A retired record, which is reclaimed by a job on another thread.
+struct SendRecord(Retired);
+
+// the record is already safe to reclaim when the job is created and is reclaimed exactly once by
+// whichever thread runs the job
+unsafe impl Send for SendRecord {}
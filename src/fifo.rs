@@ -0,0 +1,87 @@
+//! A single global queue of retired records, which are reclaimed strictly in
+//! the order they have been retired in by all threads, which is only compiled
+//! with the `global-fifo` feature.
+//!
+//! This is much slower than the regular per-thread epoch bags and is only
+//! intended for reproducing bugs, which depend on the reclamation order.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+use conquer_once::spin::OnceCell;
+use debra_common::epoch::Epoch;
+
+use crate::global::epoch_number;
+use crate::Retired;
+
+/// The global queue of all retired records in retirement order.
+static QUEUE: OnceCell<Mutex<VecDeque<Entry>>> = OnceCell::new();
+/// The lock serializing the reclamation of records popped from the global
+/// queue, so that these are reclaimed in retirement order across all threads.
+static RECLAIMING: OnceCell<Mutex<()>> = OnceCell::new();
+
+/// Appends the given `record`, which has been retired in `retire_epoch`, to the
+/// global queue.
+#[inline]
+pub(crate) fn retire(record: Retired, retire_epoch: Epoch) {
+    queue().push_back(Entry { epoch: epoch_number(retire_epoch), record });
+}
+
+/// Reclaims all records at the front of the global queue, which are safe to
+/// reclaim in the given `global_epoch`, in retirement order.
+///
+/// Reclamation stops at the first record, which is not yet safe to reclaim,
+/// even if records retired after it are.
+/// The records are only reclaimed after the queue has been unlocked again, so
+/// their destructors may retire further records (or panic) without blocking
+/// (or poisoning) the queue.
+/// If another thread is already reclaiming records, all records are left for
+/// a later call instead.
+///
+/// # Safety
+///
+/// The `global_epoch` must not be ahead of the actual global epoch.
+#[inline]
+pub(crate) unsafe fn reclaim(global_epoch: Epoch) {
+    // a destructor further up the current thread's stack may be reclaiming as well, so blocking
+    // would deadlock
+    let _reclaiming = match RECLAIMING.get_or_init(Default::default).try_lock() {
+        Ok(guard) => guard,
+        // a panicking destructor leaves no inconsistent state behind
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+        Err(TryLockError::WouldBlock) => return,
+    };
+
+    let global_epoch = epoch_number(global_epoch);
+    let ready: Vec<Entry> = {
+        let mut queue = queue();
+        // records retired in epoch E may still be accessed by threads having announced epoch
+        // E + 1, so they are only safe to reclaim once the global epoch has reached E + 3
+        let count = queue.iter().take_while(|entry| entry.epoch + 3 <= global_epoch).count();
+        queue.drain(..count).collect()
+    };
+
+    for mut entry in ready {
+        entry.record.reclaim();
+    }
+}
+
+/// Locks and returns the global queue.
+#[inline]
+fn queue() -> MutexGuard<'static, VecDeque<Entry>> {
+    QUEUE.get_or_init(Default::default).lock().unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Entry
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A retired record and the epoch it was retired in.
+#[derive(Debug)]
+struct Entry {
+    epoch: u64,
+    record: Retired,
+}
+
+// each record is reclaimed exactly once by whichever thread pops it from the queue
+unsafe impl Send for Entry {}
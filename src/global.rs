@@ -1,15 +1,130 @@
 //! Global (static) variables and data structures.
 
-use debra_common::epoch::AtomicEpoch;
+use core::mem;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use debra_common::epoch::{AtomicEpoch, Epoch};
 use debra_common::thread::ThreadState;
 
+#[cfg(not(feature = "numa"))]
 use crate::abandoned::AbandonedQueue;
+use crate::abandoned::Iter as AbandonedIter;
 use crate::list::List;
+#[cfg(feature = "numa")]
+use crate::numa::NumaQueues;
+use crate::sealed::SealedList;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // Global variables & data structures
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[cfg(not(feature = "numa"))]
 pub(crate) static ABANDONED: AbandonedQueue = AbandonedQueue::new();
+#[cfg(feature = "numa")]
+pub(crate) static ABANDONED: NumaQueues = NumaQueues::new();
 pub(crate) static EPOCH: AtomicEpoch = AtomicEpoch::new();
 pub(crate) static THREADS: List<ThreadState> = List::new();
+/// The total number of pending records of all threads, as last published by
+/// each thread when rotating its epoch bag queues.
+pub(crate) static PENDING: AtomicUsize = AtomicUsize::new(0);
+/// The total size in bytes of the pending records of all threads, which is
+/// only maintained if a global memory budget is configured.
+pub(crate) static PENDING_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// The number of currently active threads, which only includes threads
+/// configured to eagerly advance the global epoch when all threads are
+/// quiescent.
+pub(crate) static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Abandoned queue access
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Pushes the `sealed` bags into the global queue.
+#[cfg(not(feature = "numa"))]
+#[inline]
+pub(crate) fn push_abandoned(sealed: SealedList) {
+    ABANDONED.push(sealed);
+}
+
+/// Pushes the `sealed` bags into the global queue of the current thread's NUMA
+/// node.
+#[cfg(feature = "numa")]
+#[inline]
+pub(crate) fn push_abandoned(sealed: SealedList) {
+    ABANDONED.push(crate::numa::current_node(), sealed);
+}
+
+/// Takes all abandoned bags from the global queue.
+#[cfg(not(feature = "numa"))]
+#[inline]
+pub(crate) fn take_abandoned() -> AbandonedIter {
+    ABANDONED.take_all()
+}
+
+/// Takes all abandoned bags from the global queue, preferring those of the
+/// current thread's NUMA node.
+#[cfg(feature = "numa")]
+#[inline]
+pub(crate) fn take_abandoned() -> AbandonedIter {
+    ABANDONED.take_all(crate::numa::current_node())
+}
+
+/// Takes all abandoned bags from the global queue.
+#[cfg(not(feature = "numa"))]
+#[inline]
+pub(crate) fn take_every_abandoned() -> AbandonedIter {
+    ABANDONED.take_all()
+}
+
+/// Takes all abandoned bags from the global queues of all NUMA nodes.
+#[cfg(feature = "numa")]
+#[inline]
+pub(crate) fn take_every_abandoned() -> impl Iterator<Item = <AbandonedIter as Iterator>::Item> {
+    ABANDONED.take_every()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Epoch numbers
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Returns the logical number of the given `epoch`, i.e. the number of times
+/// the global epoch had been advanced when `epoch` was current.
+#[inline]
+pub(crate) fn epoch_number(epoch: Epoch) -> u64 {
+    let increment = (Epoch::new() + 1).into_inner();
+    (epoch.into_inner() / increment) as u64
+}
+
+/// Returns the number of bits available for epoch numbers, i.e. the width of
+/// the epoch representation without any bits reserved for flags.
+#[inline]
+pub(crate) fn epoch_width_bits() -> u32 {
+    let increment = (Epoch::new() + 1).into_inner();
+    (mem::size_of::<usize>() * 8) as u32 - increment.trailing_zeros()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Epoch advance hook
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The address of the function invoked after each advance of the global epoch
+/// or 0, if none is set.
+static ADVANCE_HOOK: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the `hook` invoked after each advance of the global epoch.
+#[inline]
+pub(crate) fn set_advance_hook(hook: fn(u64, u64)) {
+    ADVANCE_HOOK.store(hook as usize, Ordering::Release);
+}
+
+/// Invokes the hook, if any is set, for an advance of the global epoch from
+/// the given `old_epoch`.
+#[inline]
+pub(crate) fn notify_advance(old_epoch: Epoch) {
+    let hook = ADVANCE_HOOK.load(Ordering::Acquire);
+    if hook != 0 {
+        // the address has been stored from a function pointer of the same type
+        let hook: fn(u64, u64) = unsafe { mem::transmute(hook) };
+        hook(epoch_number(old_epoch), epoch_number(old_epoch + 1));
+    }
+}
@@ -9,15 +9,19 @@
 //! creating new ones is re-entrant and only the guard created first has to
 //! globally announce the thread as active.
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
 use core::sync::atomic::Ordering;
 
 use debra_common::{reclaim, LocalAccess};
 use reclaim::prelude::*;
 use reclaim::{AcquireResult, MarkedPtr, NotEqualError};
 
+use crate::deferred::Deferred;
 use crate::local::Local;
 use crate::typenum::Unsigned;
-use crate::{Atomic, Debra, Shared};
+use crate::{Atomic, Debra, Retired, RetiredExt, Shared};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // Guard
@@ -25,6 +29,26 @@ use crate::{Atomic, Debra, Shared};
 
 /// A region guard that prevents reclamation of records by other threads during
 /// its lifetime.
+///
+/// The thread local state accessed by guards is not synchronized, so guards
+/// are bound to the thread they were created on and can not be sent to other
+/// threads:
+///
+/// ```compile_fail
+/// use std::thread;
+///
+/// let guard = debra::Guard::new();
+/// thread::spawn(move || drop(guard)).join().unwrap();
+/// ```
+///
+/// # Nesting
+///
+/// Only the outermost guard of a thread announces the thread as active, any
+/// nested guards (including clones) merely increment a thread local counter.
+/// Nonetheless, protecting a value once under the outermost guard and passing
+/// `&Guard` references (or the protected values themselves) inward is cheaper
+/// than creating nested guards, since all values remain protected for as long
+/// as the outermost guard is alive.
 pub struct Guard<L: LocalAccess> {
     local_access: L,
 }
@@ -38,6 +62,43 @@ impl<'a> Guard<&'a Local> {
     pub fn new(local_access: &'a Local) -> Self {
         Self::with_local_access(local_access)
     }
+
+    /// Returns the nesting depth of the thread's live guards, including this
+    /// one, i.e. 1 for a single outermost guard.
+    #[inline]
+    pub fn pin_count(&self) -> usize {
+        self.local_access.guard_count()
+    }
+
+    /// Returns `true` if this is the thread's only live guard, i.e. the
+    /// outermost guard without any nested guards or clones.
+    #[inline]
+    pub fn is_outermost(&self) -> bool {
+        self.pin_count() == 1
+    }
+}
+
+impl Guard<&'static Local> {
+    /// Creates a new [`Guard`] from a raw pointer to thread local [`Local`]
+    /// state.
+    ///
+    /// This is intended for integrations, which manage the lifetime of the
+    /// [`Local`] manually, e.g. by storing it in thread-specific data of a
+    /// foreign runtime.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `local` is non-null, properly aligned and
+    /// points to a valid [`Local`], which **outlives the returned guard and all
+    /// its clones**.
+    /// The [`Local`] must not be dropped or moved before all of these guards
+    /// have been dropped, otherwise the guards access freed memory.
+    /// Like the [`Local`] itself, the guard must only be used by the thread
+    /// that owns the [`Local`].
+    #[inline]
+    pub unsafe fn from_raw_local(local: *const Local) -> Self {
+        Self::with_local_access(&*local)
+    }
 }
 
 impl<L: LocalAccess> Guard<L> {
@@ -49,6 +110,110 @@ impl<L: LocalAccess> Guard<L> {
     }
 }
 
+impl<L: LocalAccess<Reclaimer = Debra>> Guard<L> {
+    /// Loads the values of all `atomics` using the given memory `order` and
+    /// returns them in a `Vec`.
+    ///
+    /// Since the guard protects the entire region of its lifetime, all loaded
+    /// values remain valid for as long as the guard is alive, without any
+    /// additional per-element protection.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn protect_slice<'g, T, N: Unsigned>(
+        &'g self,
+        atomics: &'g [Atomic<T, N>],
+        order: Ordering,
+    ) -> Vec<Marked<Shared<'g, T, N>>> {
+        self.protect_slice_iter(atomics, order).collect()
+    }
+
+    /// Returns an iterator lazily loading the values of all `atomics` using the
+    /// given memory `order`.
+    ///
+    /// Since the guard protects the entire region of its lifetime, all loaded
+    /// values remain valid for as long as the guard is alive, without any
+    /// additional per-element protection.
+    #[inline]
+    pub fn protect_slice_iter<'g, T, N: Unsigned>(
+        &'g self,
+        atomics: &'g [Atomic<T, N>],
+        order: Ordering,
+    ) -> impl Iterator<Item = Marked<Shared<'g, T, N>>> + 'g {
+        atomics.iter().map(move |atomic| unsafe { Marked::from_marked_ptr(atomic.load_raw(order)) })
+    }
+
+    /// Defers the invocation of the closure `f` until all threads, which are
+    /// currently active, have become inactive at least once.
+    ///
+    /// The closure is retired like a record and invoked by whichever thread
+    /// eventually reclaims it, which allows scheduling arbitrary cleanup
+    /// instead of only dropping values.
+    ///
+    /// # Panics
+    ///
+    /// In *std* environments, a panic inside `f` is caught when the closure is
+    /// invoked, so that it does not unwind through the reclaiming thread's
+    /// epoch bags, and is reported to `stderr` and counted (see
+    /// [`Debra::deferred_panics`][crate::Debra::deferred_panics]).
+    ///
+    /// In `no_std` environments, panics can not be caught, so a panic inside
+    /// `f` unwinds through the reclaiming thread's epoch bags and leaks all
+    /// records remaining in the bag, which is being reclaimed, unless the
+    /// panic strategy is `abort`.
+    /// Deferred closures must hence not panic in `no_std` environments.
+    #[inline]
+    pub fn defer(&self, f: impl FnOnce() + Send + 'static) {
+        #[cfg(feature = "std")]
+        let f = move || {
+            if std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).is_err() {
+                crate::diagnostics::warn_deferred_panic();
+            }
+        };
+
+        let deferred = Deferred::new(f);
+        self.local_access.retire_record(Retired::from_box(Box::new(deferred)));
+    }
+
+    /// Protects the marked pointer returned by the `load` closure, e.g. one that
+    /// is loaded from an [`Atomic`] reached only through an accessor function
+    /// or a computed address.
+    ///
+    /// The returned value remains valid for as long as the guard is alive.
+    ///
+    /// # Safety
+    ///
+    /// The pointer returned by `load` must either be null or have been loaded
+    /// from an [`Atomic`] of the [`Debra`] reclamation scheme **while this
+    /// guard is alive**, i.e. by calling `load` here.
+    #[inline]
+    pub unsafe fn protect_with<'g, T, N: Unsigned>(
+        &'g self,
+        load: impl FnOnce() -> MarkedPtr<T, N>,
+    ) -> Marked<Shared<'g, T, N>> {
+        Marked::from_marked_ptr(load())
+    }
+
+    /// Loads the value of `atomic` using the given memory `order` and returns a
+    /// coherent snapshot of the protected value, its tag and the raw pointer.
+    ///
+    /// All parts of the snapshot are captured from a single load, which is
+    /// useful e.g. for preparing CAS loops.
+    #[inline]
+    pub fn snapshot<'g, T, N: Unsigned>(
+        &'g self,
+        atomic: &Atomic<T, N>,
+        order: Ordering,
+    ) -> PtrSnapshot<'g, T, N> {
+        let raw = atomic.load_raw(order);
+        let shared = match unsafe { Marked::from_marked_ptr(raw) } {
+            Marked::Value(shared) => Some(shared),
+            _ => None,
+        };
+
+        PtrSnapshot { shared, tag: raw.decompose_tag(), raw }
+    }
+}
+
 /***** impl Clone *********************************************************************************/
 
 impl<L: LocalAccess> Clone for Guard<L> {
@@ -111,3 +276,50 @@ unsafe impl<L: LocalAccess<Reclaimer = Debra>> Protect for Guard<L> {
 /***** impl ProtectRegion *************************************************************************/
 
 unsafe impl<L: LocalAccess<Reclaimer = Debra>> ProtectRegion for Guard<L> {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// PtrSnapshot
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A coherent snapshot of the value of an [`Atomic`], which has been captured
+/// by a single load.
+#[derive(Debug)]
+pub struct PtrSnapshot<'g, T, N: Unsigned> {
+    /// The protected value or `None`, if the loaded pointer was null.
+    pub shared: Option<Shared<'g, T, N>>,
+    /// The tag of the loaded pointer.
+    pub tag: usize,
+    /// The raw loaded pointer, including its tag.
+    pub raw: MarkedPtr<T, N>,
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::Ordering::Relaxed;
+
+    use debra_common::LocalAccess;
+
+    use crate::local::Local;
+    use crate::Atomic;
+
+    use super::Guard;
+
+    #[test]
+    fn from_raw_local() {
+        let local: *const Local = Box::into_raw(Box::new(Local::new()));
+        let atomic: Atomic<i32> = Atomic::new(1);
+
+        {
+            let guard = unsafe { Guard::from_raw_local(local) };
+            assert!(unsafe { &*local }.is_active());
+            let shared = atomic.load(Relaxed, &guard).unwrap();
+            assert_eq!(*shared, 1);
+        }
+
+        assert!(!unsafe { &*local }.is_active());
+
+        // de-allocate the record and de-register the local state
+        drop(atomic.take());
+        drop(unsafe { Box::from_raw(local as *mut Local) });
+    }
+}
@@ -8,26 +8,77 @@ extern crate alloc;
 
 #[cfg(any(test, feature = "std"))]
 mod default;
+#[cfg(any(test, feature = "std"))]
+mod executor;
+#[cfg(any(test, feature = "std"))]
+mod time;
+
+#[cfg(feature = "chaos")]
+pub mod chaos;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 mod abandoned;
+#[cfg(feature = "std")]
+mod atomic;
+mod build_info;
 mod config;
+#[cfg(feature = "debug-sync")]
+mod debug_sync;
+mod deferred;
+#[cfg(any(debug_assertions, feature = "std"))]
+mod diagnostics;
+mod epoch;
+#[cfg(feature = "global-fifo")]
+mod fifo;
 mod global;
 mod guard;
+#[cfg(feature = "lifecycle-check")]
+mod lifecycle;
 mod list;
 mod local;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "numa")]
+mod numa;
+#[cfg(feature = "std")]
+mod owned;
+mod retired;
+#[cfg(feature = "testing")]
+mod sanitizer;
 mod sealed;
+#[cfg(feature = "stats")]
+mod stats;
 
 use core::fmt;
+use core::mem;
+use core::sync::atomic::Ordering;
 
 pub use debra_common::reclaim;
 pub use reclaim::typenum;
 
-pub use crate::config::{Config, ConfigBuilder, CONFIG};
+#[cfg(feature = "std")]
+pub use crate::atomic::AtomicExt;
+pub use crate::build_info::BuildInfo;
+pub use crate::config::{Config, ConfigBuilder, ConfigError, CONFIG};
+#[cfg(feature = "std")]
+pub use crate::default::Pinned;
+pub use crate::epoch::EpochSnapshot;
+pub use crate::guard::PtrSnapshot;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::ContentionMetrics;
+#[cfg(feature = "numa")]
+pub use crate::numa::NUMA_NODE;
+#[cfg(feature = "std")]
+pub use crate::owned::OwnedExt;
+pub use crate::retired::RetiredExt;
+#[cfg(feature = "stats")]
+pub use crate::stats::ReclamationStats;
 
-#[cfg(not(feature = "std"))]
-pub use crate::local::Local;
 #[cfg(feature = "std")]
 use crate::local::Local;
+#[cfg(not(feature = "std"))]
+pub use crate::local::{Local, LocalHandle};
 
 use cfg_if::cfg_if;
 use debra_common::LocalAccess;
@@ -63,15 +114,256 @@ cfg_if! {
 }
 
 type Retired = reclaim::Retired<Debra>;
+type BagPool = debra_common::bag::BagPool<Debra>;
+type EpochBagQueues = debra_common::bag::EpochBagQueues<Debra>;
+
+/// Creates a new [`Retired`] record from the given `unlinked` record.
+#[inline]
+unsafe fn retired_from_unlinked<T, N: Unsigned>(unlinked: Unlinked<T, N>) -> Retired {
+    let unmarked = unlinked.into_marked_non_null().decompose_non_null();
+    crate::retired::typed::<T, _>(unmarked)
+}
+
+/// Retires the given `record` directly into the global queue of abandoned
+/// records, bypassing the calling thread's epoch bags.
+#[inline]
+fn abandon_record(record: Retired) {
+    let mut bags = EpochBagQueues::new();
+    bags.retire_record(record, &mut BagPool::new());
+
+    // the record is sealed with the global epoch observed after it has been unlinked
+    let epoch = crate::global::EPOCH.load(Ordering::SeqCst);
+    #[cfg(feature = "stats")]
+    crate::stats::record_retired();
+    if let Some(sealed) = crate::sealed::SealedList::from_bags(bags, epoch, [1, 0, 0]) {
+        crate::global::push_abandoned(sealed);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Free functions
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Creates a new [`Guard`], which marks the current thread as active.
+///
+/// This mirrors `crossbeam_epoch::pin` for easing the migration from
+/// `crossbeam-epoch` and is equivalent to [`Guard::new`][crate::Guard::new].
+/// The remaining API maps as follows:
+///
+/// | `crossbeam-epoch`      | `debra`                         |
+/// |------------------------|---------------------------------|
+/// | `pin()`                | [`pin`]                         |
+/// | `is_pinned()`          | [`is_pinned`]                   |
+/// | `Guard::defer`         | [`Guard::defer`][crate::Guard::defer] |
+/// | `Guard::defer_destroy` | [`Unlinked::retire`][reclaim::Unlinked::retire] |
+/// | `Guard::flush`         | [`Debra::try_flush`][reclaim::GlobalReclaim::try_flush] |
+#[cfg(feature = "std")]
+#[inline]
+pub fn pin() -> Guard {
+    Guard::new()
+}
+
+/// Returns `true` if the current thread is active, i.e. has at least one live
+/// [`Guard`].
+///
+/// This mirrors `crossbeam_epoch::is_pinned` and is equivalent to
+/// [`Debra::is_thread_active`].
+#[cfg(feature = "std")]
+#[inline]
+pub fn is_pinned() -> bool {
+    Debra::is_thread_active()
+}
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // Debra
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Distributed epoch based reclamation.
+///
+/// # Reclamation order
+///
+/// Each thread reclaims its retired records strictly oldest epoch first:
+/// All records a thread has retired in some epoch are reclaimed before any
+/// of the records it has retired in a later epoch.
+/// Hence, e.g. a parent record may be retired in an earlier epoch than its
+/// children, if it must only be freed after them.
+/// No order is guaranteed among the records a thread retires within the same
+/// epoch, nor among the records of different threads, which includes any
+/// adopted records of exited threads.
 #[derive(Copy, Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Debra;
 
+/********** impl inherent *************************************************************************/
+
+impl Debra {
+    /// Retires the given `unlinked` record directly into the global queue of
+    /// abandoned records, from where it is eventually adopted and reclaimed by
+    /// some other thread.
+    ///
+    /// This bypasses the calling thread's own epoch bags, which is useful for
+    /// short-lived threads that retire only a handful of records before
+    /// exiting.
+    /// However, each call requires a separate allocation for sealing the record
+    /// and a push to the global queue, so this is much slower than regular
+    /// retirement for threads retiring many records.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for [`retire`][GlobalReclaim::retire]
+    /// apply.
+    #[inline]
+    pub unsafe fn retire_abandon<T: 'static, N: Unsigned>(unlinked: Unlinked<T, N>) {
+        abandon_record(retired_from_unlinked(unlinked));
+    }
+
+    /// Returns the number of low bits of a pointer to a record of type `T`,
+    /// which are always zero due to the record's alignment and are hence
+    /// available for tagging.
+    ///
+    /// This is the largest valid number of tag bits `N` for an
+    /// [`Atomic<T, N>`][Atomic].
+    #[inline]
+    pub fn max_tag_bits<T>() -> u32 {
+        mem::align_of::<reclaim::Record<T, Debra>>().trailing_zeros()
+    }
+
+    /// Sets the function `f`, which is invoked with the old and the new epoch
+    /// number (see [`EpochSnapshot::number`]) after every successful advance of
+    /// the global epoch, replacing any previously set function.
+    ///
+    /// The function is invoked by the thread advancing the epoch in the hot
+    /// path of reclamation, so it must be cheap and must not block (e.g. only
+    /// increment a counter or store the new epoch), otherwise it delays all
+    /// reclamation.
+    /// It should neither create guards nor retire records.
+    #[inline]
+    pub fn on_epoch_advance(f: fn(old: u64, new: u64)) {
+        crate::global::set_advance_hook(f);
+    }
+
+    /// Returns the number of currently registered threads.
+    ///
+    /// Threads are counted from the moment their registration is complete
+    /// until their de-registration has begun, so the count is only exact while
+    /// no threads are concurrently registering or exiting.
+    #[inline]
+    pub fn thread_count() -> usize {
+        crate::global::THREADS.len()
+    }
+
+    /// Returns the compile-time configuration of the crate, i.e. the enabled
+    /// features and the compiled-in constants.
+    #[inline]
+    pub fn build_info() -> BuildInfo {
+        BuildInfo::new()
+    }
+
+    /// Returns a snapshot of the current global epoch.
+    ///
+    /// The difference between two snapshots (see
+    /// [`EpochSnapshot::wrapping_distance`]) is the number of epoch advances
+    /// in between, which can e.g. be correlated with the lag of reclamation.
+    #[inline]
+    pub fn current_epoch() -> EpochSnapshot {
+        EpochSnapshot::load()
+    }
+
+    /// Returns the total size in bytes of the pending records of all threads,
+    /// which is only maintained if a global memory budget is configured (see
+    /// [`ConfigBuilder::global_memory_budget`]).
+    #[inline]
+    pub fn global_pending_bytes() -> usize {
+        crate::global::PENDING_BYTES.load(Ordering::Relaxed)
+    }
+
+    /// Converts the given `unlinked` record back into an [`Owned`] value, which
+    /// is dropped immediately when it goes out of scope, instead of deferring
+    /// its reclamation.
+    ///
+    /// The tag of `unlinked` is preserved.
+    /// Since [`Unlinked`] is defined in the `reclaim` crate, this is provided
+    /// as an associated function rather than a method.
+    ///
+    /// # Safety
+    ///
+    /// No other thread must be able to access the record, i.e. there must not
+    /// be any concurrent readers, which may have loaded a reference to it
+    /// before it was unlinked (e.g. during single-threaded teardown).
+    #[inline]
+    pub unsafe fn unlinked_into_owned<T, N: Unsigned>(unlinked: Unlinked<T, N>) -> Owned<T, N> {
+        Owned::from_marked_non_null(unlinked.into_marked_non_null())
+    }
+
+    /// Sets whether all records are reclaimed immediately when they are
+    /// retired, as if the grace period were zero.
+    ///
+    /// This is only intended for debugging single-threaded code: When
+    /// investigating a suspected reclamation-timing bug, a use-after-free then
+    /// manifests sooner and more deterministically than with deferred
+    /// reclamation, which helps telling it apart from a logic bug.
+    ///
+    /// # Safety
+    ///
+    /// While synchronous reclamation is enabled, no reference to any retired
+    /// record must be used after it has been retired, neither by any other
+    /// thread nor by the current thread (e.g. a [`Shared`] reference loaded
+    /// under a still live guard).
+    /// In particular, this must never be enabled while more than one thread
+    /// accesses any shared data.
+    #[cfg(feature = "debug-sync")]
+    #[inline]
+    pub unsafe fn set_synchronous_reclaim(synchronous: bool) {
+        crate::debug_sync::set_synchronous_reclaim(synchronous);
+    }
+
+    /// Returns a snapshot of the global counters for diagnosing contention.
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn contention_metrics() -> ContentionMetrics {
+        ContentionMetrics::load()
+    }
+
+    /// Returns a snapshot of the process-wide numbers of retired and reclaimed
+    /// records and of the currently registered threads.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats() -> ReclamationStats {
+        ReclamationStats::load()
+    }
+
+    /// Returns the total number of warnings about likely leaked guards, which
+    /// have been emitted so far by all threads.
+    ///
+    /// See [`ConfigBuilder::guard_lifetime_warn`] for configuring the guard
+    /// lifetime watchdog, which is only available in debug builds.
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub fn leaked_guard_warnings() -> usize {
+        crate::diagnostics::leaked_guard_warnings()
+    }
+
+    /// Returns the total number of warnings about threads retiring many records
+    /// while continuously active, which have been emitted so far by all
+    /// threads.
+    ///
+    /// See [`ConfigBuilder::active_retire_warn`] for configuring the threshold
+    /// of the warning, which is only available in debug builds.
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub fn active_retire_warnings() -> usize {
+        crate::diagnostics::active_retire_warnings()
+    }
+
+    /// Returns the total number of closures deferred with
+    /// [`Guard::defer`][crate::Guard::defer], which have panicked when they
+    /// were invoked by a reclaiming thread.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn deferred_panics() -> usize {
+        crate::diagnostics::deferred_panics()
+    }
+}
+
 /********** impl Display **************************************************************************/
 
 impl fmt::Display for Debra {
@@ -85,6 +377,9 @@ impl fmt::Display for Debra {
 
 unsafe impl Reclaim for Debra {
     type Local = Local;
+    // the header must remain empty: exiting threads retire their own (plain boxed) list entries
+    // and adopted abandoned bags are retired as plain boxes as well, which is only sound as long
+    // as a `Record<T, Debra>` has exactly the same layout as a `T`
     type RecordHeader = ();
 
     #[inline]
@@ -97,7 +392,6 @@ unsafe impl Reclaim for Debra {
         local: &Self::Local,
         unlinked: Unlinked<T, N>,
     ) {
-        let unmarked = unlinked.into_marked_non_null().decompose_non_null();
-        local.retire_record(Retired::new_unchecked(unmarked));
+        local.retire_sized(retired_from_unlinked(unlinked), mem::size_of::<T>());
     }
 }
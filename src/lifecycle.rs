@@ -0,0 +1,114 @@
+//! Lifecycle checks for retired records, which are only compiled with the
+//! `lifecycle-check` feature.
+//!
+//! Every retired record is registered by its address in a global registry and
+//! is removed from it again when it is reclaimed.
+//! Retiring a record, which is already pending reclamation, or reclaiming a
+//! record, which was never retired, causes a panic.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use conquer_once::spin::OnceCell;
+
+use crate::deferred::Deferred;
+use crate::{Retired, RetiredExt};
+
+/// The global registry of all currently pending retired records.
+static REGISTRY: OnceCell<Registry> = OnceCell::new();
+
+/// Registers the given `record` as retired and wraps it, so that it is
+/// unregistered again right before it is reclaimed.
+///
+/// # Panics
+///
+/// Panics, if the record is already registered, i.e. if it has been retired
+/// twice.
+#[inline]
+pub(crate) fn checked(mut record: Retired) -> Retired {
+    let address = record.address();
+    registry().insert_retired(address);
+
+    let deferred = Deferred::new(move || {
+        registry().remove_reclaimed(address);
+        unsafe { record.reclaim() };
+    });
+
+    Retired::from_box(Box::new(deferred))
+}
+
+/// Returns the global registry.
+#[inline]
+fn registry() -> &'static Registry {
+    REGISTRY.get_or_init(Registry::default)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Registry
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A set of the addresses of all records pending reclamation.
+#[derive(Debug, Default)]
+struct Registry {
+    pending: Mutex<HashSet<usize>>,
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl Registry {
+    /// Registers the record at the given `address` as retired.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the record is already registered.
+    #[inline]
+    fn insert_retired(&self, address: usize) {
+        let inserted = self.pending.lock().unwrap().insert(address);
+        assert!(inserted, "double retire of record at {:#x}", address);
+    }
+
+    /// Unregisters the record at the given `address` before it is reclaimed.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the record is not registered.
+    #[inline]
+    fn remove_reclaimed(&self, address: usize) {
+        let removed = self.pending.lock().unwrap().remove(&address);
+        assert!(removed, "reclaim of record at {:#x}, which was never retired", address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+
+    #[test]
+    fn retire_and_reclaim() {
+        let registry = Registry::default();
+        registry.insert_retired(0x1000);
+        registry.insert_retired(0x2000);
+        registry.remove_reclaimed(0x1000);
+        registry.remove_reclaimed(0x2000);
+
+        // addresses may be re-used after their records have been reclaimed
+        registry.insert_retired(0x1000);
+        registry.remove_reclaimed(0x1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "double retire")]
+    fn double_retire() {
+        let registry = Registry::default();
+        registry.insert_retired(0x1000);
+        registry.insert_retired(0x1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "never retired")]
+    fn reclaim_never_retired() {
+        let registry = Registry::default();
+        registry.insert_retired(0x1000);
+        registry.remove_reclaimed(0x2000);
+    }
+}
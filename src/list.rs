@@ -9,7 +9,10 @@ use core::marker::PhantomData;
 use core::mem;
 use core::ops::Deref;
 use core::ptr::{self, NonNull};
-use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use core::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release, SeqCst},
+};
 
 use crate::reclaim::align::CacheAligned;
 use crate::reclaim::prelude::*;
@@ -33,6 +36,7 @@ const REMOVE_TAG: usize = 0b1;
 #[derive(Debug)]
 pub(crate) struct List<T> {
     head: AtomicMarkedPtr<Node<T>>,
+    len: AtomicUsize,
 }
 
 /***** impl inherent ******************************************************************************/
@@ -40,7 +44,27 @@ pub(crate) struct List<T> {
 impl<T> List<T> {
     /// Creates a new empty [`List`].
     pub const fn new() -> Self {
-        Self { head: AtomicMarkedPtr::null() }
+        Self { head: AtomicMarkedPtr::null(), len: AtomicUsize::new(0) }
+    }
+
+    /// Returns the number of entries in the list.
+    ///
+    /// An entry is counted from the moment its insertion is complete until its
+    /// removal has begun, i.e. entries marked for removal are not counted.
+    /// Under concurrent insertions and removals, the count is only a snapshot,
+    /// which is exact only while the list is quiescent.
+    #[inline]
+    pub fn len(&self) -> usize {
+        // (LIS:7) this `SeqCst` load synchronizes-with the `SeqCst` RMWs (LIS:6) and (LIS:8)
+        self.len.load(SeqCst)
+    }
+
+    /// Returns `true` if the list contains no entries.
+    ///
+    /// Like [`len`][List::len], this is only a snapshot under contention.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     /// Inserts the given `entry` and returns an owned [`SetEntry`] token.
@@ -50,8 +74,22 @@ impl<T> List<T> {
     #[inline]
     pub fn insert(&self, entry: T) -> ListEntry<T> {
         let entry = Box::leak(Box::new(Node::new(entry)));
+
+        // when the first threads register (e.g. at program start), the list is likely empty and
+        // a single CAS from null suffices, since the new node's `next` pointer is already null;
+        // if another thread inserts first, the CAS fails and the regular loop is used instead
+        // (LIS:9) this `Release` CAS synchronizes-with the `Acquire` load (LIS:1), (LIS:4),
+        // (LIS:5) and the `Acquire` CAS (LIS:3)
+        if self
+            .head
+            .compare_exchange(MarkedPtr::null(), MarkedPtr::new(entry), Release, Relaxed)
+            .is_ok()
+        {
+            return self.inserted(entry);
+        }
+
         loop {
-            // (LIS:1) this `Acquire` load synchronizes-with `Release` CAS (LIS:2)
+            // (LIS:1) this `Acquire` load synchronizes-with `Release` CAS (LIS:2) and (LIS:9)
             let head = self.head.load(Acquire);
             entry.next().store(head, Relaxed);
 
@@ -62,8 +100,11 @@ impl<T> List<T> {
                 .compare_exchange_weak(head, MarkedPtr::new(entry), Release, Relaxed)
                 .is_ok()
             {
-                return ListEntry(NonNull::from(entry), PhantomData);
+                return self.inserted(entry);
             }
+
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_list_insert_retry();
         }
     }
 
@@ -91,14 +132,21 @@ impl<T> List<T> {
             let next = MarkedPtr::new(pos.next.unwrap_ptr());
             let next_marked = MarkedPtr::compose(pos.next.unwrap_ptr(), REMOVE_TAG);
 
-            // (LIS:3) this `Acquire` CAS synchronizes-with the `Release` CAS (LIS:2)
+            // (LIS:3) this `Acquire` CAS synchronizes-with the `Release` CAS (LIS:2) and (LIS:9)
             if curr.next.compare_exchange(next, next_marked, Acquire, Relaxed).is_err() {
                 continue;
             }
 
+            // the entry is logically removed once it is marked, so it is uncounted exactly once,
+            // even if the physical unlinking below has to be repeated
+            // (LIS:8) this `SeqCst` RMW synchronizes-with the `SeqCst` load (LIS:7)
+            self.len.fetch_sub(1, SeqCst);
+
             // (LIS:4) this `Release` CAS synchronizes-with the `Acquire` loads (INN:3), (INN:4),
             // (LIS:4), (LIS:5) and the `Acquire` CAS (LIS:2)
             if prev.compare_exchange(MarkedPtr::from(curr), next, Release, Relaxed).is_err() {
+                #[cfg(feature = "metrics")]
+                crate::metrics::record_list_repeat_remove();
                 self.repeat_remove(entry);
             }
 
@@ -112,6 +160,14 @@ impl<T> List<T> {
         Iter::new(self, &self.head)
     }
 
+    /// Counts the successfully inserted `entry` and returns its token.
+    #[inline]
+    fn inserted(&self, entry: &Node<T>) -> ListEntry<T> {
+        // (LIS:6) this `SeqCst` RMW synchronizes-with the `SeqCst` load (LIS:7)
+        self.len.fetch_add(1, SeqCst);
+        ListEntry(NonNull::from(entry), PhantomData)
+    }
+
     /// Loops until a marked node containing `entry` is successfully removed.
     #[inline]
     fn repeat_remove(&self, entry: NonNull<Node<T>>) {
@@ -309,7 +365,8 @@ impl<T> Iterator for IterInner<'_, T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        // (LIS:4) this `Acquire` load synchronizes-with the the `Release` CAS (LIS:1) and (LIS:3)
+        // (LIS:4) this `Acquire` load synchronizes-with the the `Release` CAS (LIS:1), (LIS:3) and
+        // (LIS:9)
         while let Value(curr) = unsafe { MarkedNonNull::new(self.prev.as_ref().load(Acquire)) } {
             let (curr, curr_tag) = unsafe { curr.decompose_ref_unbounded() };
             if curr_tag == REMOVE_TAG {
@@ -318,7 +375,8 @@ impl<T> Iterator for IterInner<'_, T> {
             }
 
             let curr_next = curr.next();
-            // (LIS:5) this `Acquire` load synchronizes-with the `Release` CAS (LIS:1) and (LIS:3)
+            // (LIS:5) this `Acquire` load synchronizes-with the `Release` CAS (LIS:1), (LIS:3) and
+            // (LIS:9)
             let next = curr_next.load(Acquire);
 
             if unsafe { self.prev.as_ref().load(Relaxed) } != MarkedPtr::from(curr) {
@@ -395,6 +453,7 @@ impl<T> UnwrapPtr for Option<NonNull<T>> {
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{Arc, Barrier};
     use std::thread::{self, ThreadId};
 
     use super::List;
@@ -420,4 +479,57 @@ mod tests {
             assert!(LIST.head.load(Relaxed).is_null());
         }
     }
+
+    #[test]
+    fn len() {
+        let list = List::new();
+        assert!(list.is_empty());
+
+        let first = list.insert(1);
+        let second = list.insert(2);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+
+        let _ = list.remove(first); // deliberately leaks memory
+        assert_eq!(list.len(), 1);
+        let _ = list.remove(second);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn concurrent_first_inserts() {
+        for _ in 0..10_000 {
+            // a fresh (leaked) list for each iteration, so both threads race for the empty head
+            let list: &'static List<usize> = Box::leak(Box::new(List::new()));
+            let barrier = Arc::new(Barrier::new(3));
+
+            let handles: Vec<_> = (0..2)
+                .map(|id| {
+                    let barrier = Arc::clone(&barrier);
+                    thread::spawn(move || {
+                        barrier.wait();
+                        let token = list.insert(id);
+                        // wait until both entries have been checked
+                        barrier.wait();
+                        barrier.wait();
+                        let _ = list.remove(token); // deliberately leaks memory
+                    })
+                })
+                .collect();
+
+            barrier.wait();
+            barrier.wait();
+            assert_eq!(list.len(), 2);
+            let mut ids: Vec<_> = list.iter().copied().collect();
+            ids.sort();
+            assert_eq!(ids, [0, 1]);
+            barrier.wait();
+
+            for handle in handles {
+                handle.join().unwrap();
+            }
+
+            assert!(list.head.load(Relaxed).is_null());
+        }
+    }
 }
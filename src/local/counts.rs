@@ -0,0 +1,138 @@
+//! Bookkeeping of the number of records in each epoch bag queue.
+
+use core::mem;
+
+use debra_common::epoch::PossibleAge;
+
+/// The number of epoch bag queues of each thread.
+pub(crate) const BAG_QUEUE_COUNT: usize = 3;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// BagCounts
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The number of records and their total size in bytes in each of a thread's
+/// epoch bag queues.
+///
+/// The counts mirror the rotation of the queues, so the count at `curr_idx`
+/// always belongs to the queue records are currently retired in.
+/// Adopted abandoned bags are counted as a single record each, but without
+/// any size, since the sizes of their records are unknown.
+#[derive(Debug, Default)]
+pub(super) struct BagCounts {
+    counts: [usize; BAG_QUEUE_COUNT],
+    bytes: [usize; BAG_QUEUE_COUNT],
+    curr_idx: usize,
+    #[cfg(feature = "testing")]
+    rotations: u64,
+    #[cfg(feature = "stats")]
+    adopted: [usize; BAG_QUEUE_COUNT],
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl BagCounts {
+    /// Increments the count of the current epoch's queue and adds `size` to
+    /// its bytes.
+    #[inline]
+    pub fn retire(&mut self, size: usize) {
+        self.counts[self.curr_idx] += 1;
+        self.bytes[self.curr_idx] += size;
+    }
+
+    /// Increments the count of the queue matching the given `age`.
+    #[inline]
+    pub fn retire_by_age(&mut self, age: PossibleAge) {
+        let idx = match age {
+            PossibleAge::SameEpoch => self.curr_idx,
+            PossibleAge::OneEpoch => (self.curr_idx + 2) % BAG_QUEUE_COUNT,
+            PossibleAge::TwoEpochs => (self.curr_idx + 1) % BAG_QUEUE_COUNT,
+        };
+
+        self.counts[idx] += 1;
+        #[cfg(feature = "stats")]
+        {
+            self.adopted[idx] += 1;
+        }
+    }
+
+    /// Rotates the counts like the queues they belong to, resetting the count
+    /// of the oldest queue, which becomes the current one.
+    ///
+    /// Returns the number of reclaimed records.
+    #[inline]
+    pub fn rotate(&mut self) -> usize {
+        self.curr_idx = (self.curr_idx + 1) % BAG_QUEUE_COUNT;
+        #[cfg(feature = "testing")]
+        {
+            self.rotations += 1;
+        }
+        self.bytes[self.curr_idx] = 0;
+        #[cfg(feature = "stats")]
+        {
+            // adopted bags are counted when their own records are reclaimed
+            let adopted = mem::replace(&mut self.adopted[self.curr_idx], 0);
+            crate::stats::record_reclaimed(self.counts[self.curr_idx] - adopted);
+        }
+        mem::replace(&mut self.counts[self.curr_idx], 0)
+    }
+
+    /// Returns the index of the current epoch's queue.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn curr_idx(&self) -> usize {
+        self.curr_idx
+    }
+
+    /// Returns the number of rotations performed so far.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn rotations(&self) -> u64 {
+        self.rotations
+    }
+
+    /// Returns the total number of records in all queues.
+    #[inline]
+    pub fn pending_records(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the total size in bytes of all records in all queues.
+    #[inline]
+    pub fn pending_bytes(&self) -> usize {
+        self.bytes.iter().sum()
+    }
+
+    /// Returns the numbers of records retired by the thread itself (i.e.
+    /// excluding any adopted bags), sorted like [`sorted`][BagCounts::sorted].
+    ///
+    /// Adopted bags are only tracked with the `stats` feature, otherwise these
+    /// are the same as the sorted counts.
+    #[inline]
+    pub fn own_sorted(&self) -> [usize; BAG_QUEUE_COUNT] {
+        #[cfg(feature = "stats")]
+        {
+            let idx = self.curr_idx;
+            let [c0, c1, c2] = self.sorted();
+            [
+                c0 - self.adopted[idx],
+                c1 - self.adopted[(idx + 2) % BAG_QUEUE_COUNT],
+                c2 - self.adopted[(idx + 1) % BAG_QUEUE_COUNT],
+            ]
+        }
+        #[cfg(not(feature = "stats"))]
+        self.sorted()
+    }
+
+    /// Returns the counts sorted by the age of their queues, starting with the
+    /// current one.
+    #[inline]
+    pub fn sorted(&self) -> [usize; BAG_QUEUE_COUNT] {
+        let idx = self.curr_idx;
+        [
+            self.counts[idx],
+            self.counts[(idx + 2) % BAG_QUEUE_COUNT],
+            self.counts[(idx + 1) % BAG_QUEUE_COUNT],
+        ]
+    }
+}
@@ -0,0 +1,90 @@
+//! Handles for sharing thread local state without thread local storage.
+
+use alloc::boxed::Box;
+
+use crate::LocalGuard;
+
+use super::Local;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// LocalHandle
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A copyable handle to a [`Local`], which lives for the remainder of the
+/// program.
+///
+/// In `no_std` environments without thread local storage (e.g. the tasks of
+/// an RTOS), each task has to own its [`Local`] and pass it to every place
+/// where a guard is created.
+/// A [`LocalHandle`] can be stored by the task (e.g. in its task control
+/// block) and passed down its call stack by value.
+/// Like the [`Local`] itself, a handle is bound to the task owning it and can
+/// not be sent to other threads.
+///
+/// # Examples
+///
+/// ```ignore
+/// use debra::LocalHandle;
+///
+/// fn task_main() {
+///     // registers the task in the global list of threads
+///     let handle = LocalHandle::register();
+///     pop_twice(handle);
+///     // de-registers the task, all of its guards have been dropped
+///     unsafe { handle.deregister() };
+/// }
+///
+/// fn pop_twice(handle: LocalHandle) {
+///     let guard = handle.guard();
+///     // ...
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct LocalHandle(&'static Local);
+
+/***** impl inherent ******************************************************************************/
+
+impl LocalHandle {
+    /// Creates and globally registers a new [`Local`] and returns a handle to
+    /// it.
+    ///
+    /// The [`Local`] is allocated and leaked, unless the handle is explicitly
+    /// de-registered (see [`deregister`][LocalHandle::deregister]).
+    #[inline]
+    pub fn register() -> Self {
+        Self(Box::leak(Box::new(Local::new())))
+    }
+
+    /// Creates a new handle to the given `local`, e.g. one stored in memory
+    /// statically reserved for a task.
+    #[inline]
+    pub fn new(local: &'static Local) -> Self {
+        Self(local)
+    }
+
+    /// Returns the [`Local`] the handle refers to.
+    #[inline]
+    pub fn local(self) -> &'static Local {
+        self.0
+    }
+
+    /// Creates a new guard from the referenced [`Local`].
+    #[inline]
+    pub fn guard(self) -> LocalGuard<'static> {
+        LocalGuard::new(self.0)
+    }
+
+    /// De-registers and drops the [`Local`] the handle refers to, abandoning
+    /// all of its pending records.
+    ///
+    /// # Safety
+    ///
+    /// The handle must have been created by
+    /// [`register`][LocalHandle::register], no guards created from it must
+    /// be alive and neither the handle nor any of its copies must be used
+    /// afterwards.
+    #[inline]
+    pub unsafe fn deregister(self) {
+        drop(Box::from_raw(self.0 as *const Local as *mut Local));
+    }
+}
@@ -1,25 +1,40 @@
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
 
-use core::mem::ManuallyDrop;
-use core::ptr::{self, NonNull};
+use core::cmp;
+use core::mem;
 use core::sync::atomic::Ordering::{Acquire, Relaxed, Release, SeqCst};
 
-use debra_common::epoch::Epoch;
+use debra_common::epoch::{Epoch, PossibleAge};
 use debra_common::thread::{
     State::{Active, Inactive},
     ThreadState,
 };
 
+use crate::abandoned::Iter as AbandonedIter;
 use crate::config::{Config, CONFIG};
-use crate::global::{ABANDONED, EPOCH, THREADS};
-use crate::sealed::SealedList;
-use crate::Retired;
+#[cfg(all(debug_assertions, not(feature = "global-fifo")))]
+use crate::diagnostics::RecentRetires;
+use crate::global::{self, epoch_number, EPOCH, THREADS};
+use crate::sealed::{Sealed, SealedList};
+use crate::{Retired, RetiredExt};
+
+use super::counts::{BagCounts, BAG_QUEUE_COUNT};
 
 type BagPool = debra_common::bag::BagPool<crate::Debra>;
 type EpochBagQueues = debra_common::bag::EpochBagQueues<crate::Debra>;
 type ThreadStateIter = crate::list::Iter<'static, ThreadState>;
 
+/// The number of pending records, at or above which a thread lowers its
+/// thresholds in adaptive mode.
+const ADAPTIVE_PENDING_LIMIT: usize = 4096;
+/// The maximum factor by which a thread raises its thresholds above the
+/// configured ones in adaptive mode.
+const ADAPTIVE_MAX_BACKOFF: u32 = 8;
+/// The maximum number of reclamation attempts a retiring thread performs
+/// before exceeding the global memory budget.
+const GLOBAL_BUDGET_ATTEMPTS: u32 = 1024;
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // LocalInner
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -30,10 +45,16 @@ pub(super) struct LocalInner {
     /// The counter for determining when to attempt to advance the
     /// global epoch
     advance_count: u32,
-    /// The epoch bags used for caching retired records
-    bags: ManuallyDrop<EpochBagQueues>,
+    /// The effective advance threshold, which is adjusted at runtime in
+    /// adaptive mode
+    advance_threshold: u32,
+    /// The epoch bags used for caching retired records, which are only
+    /// allocated once the thread retires its first record
+    bags: Option<EpochBagQueues>,
     /// The thread local pool for allocating new bags
     bag_pool: BagPool,
+    /// The number of records in each of the epoch bags
+    bag_counts: BagCounts,
     /// The cached value of the last observed global epoch value
     cached_local_epoch: Epoch,
     /// The flag determining whether a thread is able to advance the
@@ -42,9 +63,31 @@ pub(super) struct LocalInner {
     /// The counter for determining when to perform the advance check on the
     /// next thread
     check_count: u32,
+    /// The effective check threshold, which is adjusted at runtime in adaptive
+    /// mode
+    check_threshold: u32,
     /// The copy of the global configuration that is read once during
     /// a thread's creation
     config: Config,
+    /// The number of operations performed while holding the current outermost
+    /// guard
+    #[cfg(debug_assertions)]
+    guard_ops: u32,
+    /// The number of records retired while holding the current outermost guard
+    #[cfg(debug_assertions)]
+    active_retires: u32,
+    /// The addresses of the most recently retired records for detecting
+    /// double retires
+    #[cfg(all(debug_assertions, not(feature = "global-fifo")))]
+    recent_retires: RecentRetires,
+    /// The number of bag rotations that have been deferred, if lazy rotation
+    /// is configured
+    pending_rotations: u32,
+    /// The number of pending records last published to the global total
+    published_pending: usize,
+    /// The number of pending bytes last published to the global total, if a
+    /// global memory budget is configured
+    published_bytes: usize,
     /// The iterator over all globally registered threads
     thread_iter: ThreadStateIter,
 }
@@ -52,39 +95,108 @@ pub(super) struct LocalInner {
 /***** impl inherent ******************************************************************************/
 
 impl LocalInner {
-    /// Creates a new [`LocalInner`].
+    /// Creates a new [`LocalInner`] with a copy of the global configuration.
     #[inline]
     pub fn new(global_epoch: Epoch) -> Self {
+        Self::with_config(global_epoch, CONFIG.try_get().copied().unwrap_or_default())
+    }
+
+    /// Creates a new [`LocalInner`] with the given `config`.
+    #[inline]
+    pub fn with_config(global_epoch: Epoch, config: Config) -> Self {
         Self {
             advance_count: 0,
-            bags: ManuallyDrop::new(EpochBagQueues::new()),
+            advance_threshold: config.advance_threshold(),
+            bags: None,
             bag_pool: BagPool::new(),
+            bag_counts: BagCounts::default(),
             cached_local_epoch: global_epoch,
             can_advance: false,
-            config: CONFIG.try_get().copied().unwrap_or_default(),
             check_count: 0,
+            check_threshold: config.check_threshold(),
+            config,
+            #[cfg(debug_assertions)]
+            guard_ops: 0,
+            #[cfg(debug_assertions)]
+            active_retires: 0,
+            #[cfg(all(debug_assertions, not(feature = "global-fifo")))]
+            recent_retires: RecentRetires::new(),
+            pending_rotations: 0,
+            published_pending: 0,
+            published_bytes: 0,
             thread_iter: THREADS.iter(),
         }
     }
 
     /// Attempts to reclaim the retired records in the oldest epoch bag queue.
+    ///
+    /// If the global epoch has been advanced since the thread last observed it,
+    /// the epoch bag queues are rotated and the oldest queue, which is then
+    /// safe to reclaim, is flushed.
+    ///
+    /// Must only be called while the associated thread is inactive.
     #[inline]
     pub fn try_flush(&mut self, thread_state: &ThreadState) {
+        let prev_epoch = self.cached_local_epoch;
         let global_epoch = self.acquire_and_assess_global_epoch();
+        if self.pending_rotations > 0 {
+            self.rotate_pending();
+        }
 
-        if self.cached_local_epoch != global_epoch {
+        if prev_epoch != global_epoch {
             // irrelevant for other threads since the thread remains inactive
             thread_state.store(global_epoch, Inactive, Relaxed);
         }
     }
 
+    /// Drives the incremental checks to completion by visiting all remaining
+    /// threads at once and advances the global epoch, if all of them are either
+    /// inactive or have announced the current global epoch.
+    ///
+    /// Returns `true` if the global epoch has been advanced by this call, which
+    /// is not the case, if any thread is still lagging behind.
+    /// Afterwards, the thread's local epoch is updated as by
+    /// [`try_flush`][LocalInner::try_flush].
+    #[inline]
+    pub fn try_advance_epoch(&mut self, thread_state: &ThreadState) -> bool {
+        let global_epoch = self.acquire_and_assess_global_epoch();
+        while !self.can_advance && self.visit_next_thread(thread_state, global_epoch) {}
+        let advanced = self.can_advance && advance_global_epoch(global_epoch);
+
+        self.try_flush(thread_state);
+        advanced
+    }
+
+    /// Marks the end of a batch of operations, so that the next incremental
+    /// check for advancing the global epoch is performed right away, if the
+    /// thread is `active`, or otherwise when it next becomes active.
+    #[inline]
+    pub fn checkpoint(&mut self, thread_state: &ThreadState, active: bool) {
+        if active {
+            self.check_count = 0;
+            // the epoch must only be advanced relative to the epoch, which the thread has actually
+            // announced, even if it has already observed a later one in the meantime
+            let (announced, _) = thread_state.load(Relaxed);
+            self.try_advance(thread_state, announced);
+        } else {
+            self.check_count = self.check_threshold.saturating_sub(1);
+        }
+    }
+
     /// Marks the associated thread as active.
     #[inline]
     pub fn set_active(&mut self, thread_state: &ThreadState) {
+        #[cfg(debug_assertions)]
+        {
+            self.guard_ops = 0;
+            self.active_retires = 0;
+        }
+
         let global_epoch = self.acquire_and_assess_global_epoch();
 
         self.check_count += 1;
-        if self.check_count == self.config.check_threshold() {
+        // in adaptive mode, the threshold may have been lowered below the current count
+        if self.check_count >= self.check_threshold {
             self.check_count = 0;
             self.try_advance(thread_state, global_epoch);
         }
@@ -96,6 +208,30 @@ impl LocalInner {
         thread_state.store(global_epoch, Active, SeqCst);
     }
 
+    /// Counts an operation performed while the associated thread is active and
+    /// warns about a likely leaked guard once the configured threshold is
+    /// reached.
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub fn record_guard_op(&mut self) {
+        self.guard_ops = self.guard_ops.saturating_add(1);
+        if self.guard_ops == self.config.guard_lifetime_warn() {
+            crate::diagnostics::warn_leaked_guard(self.guard_ops);
+        }
+    }
+
+    /// Counts a record retired while the associated thread is active and warns
+    /// about retiring while continuously active once the configured threshold
+    /// is reached.
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub fn record_active_retire(&mut self) {
+        self.active_retires = self.active_retires.saturating_add(1);
+        if self.active_retires == self.config.active_retire_warn() {
+            crate::diagnostics::warn_active_retires(self.active_retires);
+        }
+    }
+
     /// Marks the associated thread as inactive.
     #[inline]
     pub fn set_inactive(&self, thread_state: &ThreadState) {
@@ -104,10 +240,234 @@ impl LocalInner {
         thread_state.store(self.cached_local_epoch, Inactive, Release);
     }
 
-    /// Retires the given `record` in the current epoch's bag queue.
+    /// Counts the associated thread, which is about to create its outermost
+    /// guard, as active for quiescent advancing.
+    ///
+    /// Unlike [`set_active`][LocalInner::set_active], this must only be called
+    /// once per outermost guard, but not when rejoining the scans.
+    #[inline]
+    pub fn enter_active(&self) {
+        if self.config.quiescent_advance() {
+            global::ACTIVE.fetch_add(1, SeqCst);
+        }
+    }
+
+    /// Returns `true` if the associated thread, which has just become
+    /// inactive, has been the last active thread and the global epoch should
+    /// be eagerly advanced.
+    #[inline]
+    pub fn leave_active(&self) -> bool {
+        self.config.quiescent_advance() && global::ACTIVE.fetch_sub(1, SeqCst) == 1
+    }
+
+    /// Advances the global epoch up to [`BAG_QUEUE_COUNT`] times, for as long
+    /// as all threads remain quiescent, which reclaims all of the thread's
+    /// records.
+    ///
+    /// Each advance requires a full scan of all threads as for
+    /// [`try_advance_epoch`][LocalInner::try_advance_epoch], the global
+    /// counter of active threads only serves as the trigger.
+    #[cold]
+    pub fn advance_quiescent(&mut self, thread_state: &ThreadState) {
+        for _ in 0..BAG_QUEUE_COUNT {
+            if global::ACTIVE.load(SeqCst) != 0 || !self.try_advance_epoch(thread_state) {
+                return;
+            }
+        }
+    }
+
+    /// Retires the given `record` of `size` bytes in the current epoch's bag
+    /// queue.
+    ///
+    /// If the configured byte budget is exceeded afterwards, an attempt to
+    /// reclaim the oldest records is forced.
+    #[cfg_attr(feature = "global-fifo", allow(unused_variables))]
+    #[inline]
+    pub fn retire_record(&mut self, record: Retired, size: usize) {
+        #[cfg(feature = "chaos")]
+        crate::chaos::maybe_yield();
+        // deferred rotations must be performed before any further records are retired, so that
+        // the current bag queue only contains records retired in the current local epoch
+        if self.pending_rotations > 0 {
+            self.rotate_pending();
+        }
+
+        #[cfg(feature = "lifecycle-check")]
+        let record = crate::lifecycle::checked(record);
+
+        #[cfg(feature = "debug-sync")]
+        {
+            if crate::debug_sync::is_synchronous() {
+                let mut record = record;
+                unsafe { record.reclaim() };
+                return;
+            }
+        }
+
+        // records reclaimed from the global queue may be reclaimed before the thread's bag queues
+        // have been rotated, so their addresses can be re-used earlier
+        #[cfg(all(debug_assertions, not(feature = "global-fifo")))]
+        self.recent_retires.retire(record.address());
+
+        #[cfg(feature = "testing")]
+        let record =
+            crate::sanitizer::sanitized(record, size, epoch_number(self.cached_local_epoch));
+
+        #[cfg(any(test, feature = "std"))]
+        let record = crate::executor::offloaded(record);
+
+        // the global queue has no notion of a per-thread byte budget
+        #[cfg(feature = "global-fifo")]
+        crate::fifo::retire(record, self.cached_local_epoch);
+        #[cfg(not(feature = "global-fifo"))]
+        self.retire_into_bags(record, size);
+    }
+
+    /// Retires the given `record` of `size` bytes in the current epoch's bag
+    /// queue and enforces the configured byte budget and record limit.
+    #[cfg_attr(feature = "global-fifo", allow(dead_code))]
+    #[inline]
+    fn retire_into_bags(&mut self, record: Retired, size: usize) {
+        let global_budget = self.config.global_memory_budget();
+        if global_budget > 0 {
+            self.reserve_global_bytes(size, global_budget);
+        }
+
+        #[cfg(feature = "stats")]
+        crate::stats::record_retired();
+        self.bags.get_or_insert_with(EpochBagQueues::new).retire_record(record, &mut self.bag_pool);
+        self.bag_counts.retire(size);
+
+        let budget = self.config.reclaim_byte_budget();
+        let max_records = self.config.max_buffered_records();
+        if (budget > 0 && self.bag_counts.pending_bytes() > budget)
+            || (max_records > 0 && self.bag_counts.pending_records() >= max_records)
+        {
+            self.enforce_budget();
+        }
+    }
+
+    /// Returns the number of records in each epoch bag queue, starting with the
+    /// queue of the current epoch.
+    #[inline]
+    pub fn bag_counts(&self) -> [usize; BAG_QUEUE_COUNT] {
+        self.bag_counts.sorted()
+    }
+
+    /// Returns `true` if a further re-entrantly retired record may be retired
+    /// after `retired` such records have already been retired since the last
+    /// reclamation pass, according to the configured limit and cap.
+    #[inline]
+    pub fn accepts_reentrant(&self, retired: usize) -> bool {
+        let limit = self.config.reentrant_limit();
+        let cap = self.config.reentrant_reclaim_cap();
+        // deferred rotations are performed before the next record is retired, so the current
+        // queue will be empty
+        (limit == 0 || retired < limit)
+            && (cap == 0 || self.pending_rotations > 0 || self.bag_counts.sorted()[0] < cap)
+    }
+
+    /// Returns the index of the current epoch's bag queue.
+    #[cfg(feature = "testing")]
     #[inline]
-    pub fn retire_record(&mut self, record: Retired) {
-        self.bags.retire_record(record, &mut self.bag_pool);
+    pub fn current_bag_index(&self) -> usize {
+        self.bag_counts.curr_idx()
+    }
+
+    /// Returns the number of epoch bag queue rotations performed so far.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn rotations_performed(&self) -> u64 {
+        self.bag_counts.rotations()
+    }
+
+    /// Returns the total size in bytes of all records in all epoch bag queues.
+    #[inline]
+    pub fn pending_bytes(&self) -> usize {
+        self.bag_counts.pending_bytes()
+    }
+
+    /// Returns the epoch and the number of records of each epoch bag queue,
+    /// starting with the queue of the current epoch.
+    #[inline]
+    pub fn pending_by_epoch(&self) -> [(u64, usize); BAG_QUEUE_COUNT] {
+        // with lazy rotation, the queues still belong to the epoch observed before any of the
+        // deferred rotations
+        let curr =
+            epoch_number(self.cached_local_epoch).wrapping_sub(self.pending_rotations.into());
+        let [c0, c1, c2] = self.bag_counts.sorted();
+        [(curr, c0), (curr.wrapping_sub(1), c1), (curr.wrapping_sub(2), c2)]
+    }
+
+    /// Reclaims the records in the epoch bag queue of the given `epoch`, if it
+    /// is safe to do so, and returns their number.
+    ///
+    /// Must only be called while the associated thread is inactive.
+    pub fn reclaim_epoch(&mut self, thread_state: &ThreadState, epoch: u64) -> usize {
+        let [(curr, _), _, _] = self.pending_by_epoch();
+        let age = curr.wrapping_sub(epoch);
+
+        // (INN:7) this `Acquire` load synchronizes-with the `Release` CAS (INN:4)
+        let global_epoch = EPOCH.load(Acquire);
+        // since a thread's local epoch may lag behind the global epoch by one, records retired
+        // in epoch E may still be accessed by threads having announced epoch E + 1, so they are
+        // only safe to reclaim once the global epoch has reached E + 3
+        if age >= BAG_QUEUE_COUNT as u64 || epoch_number(global_epoch) < epoch + 3 {
+            return 0;
+        }
+
+        // all older queues are rotated (and reclaimed) first, the queue of `epoch` is last
+        let rotations = BAG_QUEUE_COUNT as u32 - age as u32;
+        let mut reclaimed = 0;
+        for _ in 0..rotations {
+            reclaimed = unsafe { self.rotate_bags() };
+        }
+
+        // each rotation advances the local epoch by one, starting with any deferred rotations
+        let advances = rotations.saturating_sub(self.pending_rotations);
+        self.pending_rotations = self.pending_rotations.saturating_sub(rotations);
+        if advances > 0 {
+            self.cached_local_epoch = self.cached_local_epoch + advances as usize;
+            self.reset_incremental_checks();
+            // irrelevant for other threads since the thread remains inactive
+            thread_state.store(self.cached_local_epoch, Inactive, Relaxed);
+        }
+
+        reclaimed
+    }
+
+    /// Reclaims the records of an exiting thread, which are safe to reclaim,
+    /// before its final record is retired.
+    ///
+    /// Reclaimed records may retire further records, in which case this has to
+    /// be called again once these have been retired.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called by an exiting thread, which has already removed its
+    /// entry from the global thread list.
+    #[cold]
+    pub unsafe fn prepare_exit(&mut self) {
+        // the last thread reclaims all of its records, since nobody would adopt them otherwise;
+        // for only a few pending records, abandoning them costs more than reclaiming all records in
+        // place, which are already safe to reclaim;
+        // this must be done BEFORE the final record is retired, since it has only just been
+        // unlinked and other exiting threads may still traverse it
+        let pending: usize = self.bag_counts.sorted().iter().sum();
+        // (INN:9) this `SeqCst` load synchronizes-with the `Release` CAS (INN:4) and is ordered
+        // after the `SeqCst` RMW (LIS:8) following the unlinking of the thread's entry, so any
+        // thread still traversing the entry has announced at most the loaded epoch
+        let global_epoch = EPOCH.load(SeqCst);
+        if THREADS.len() == 0 {
+            self.reclaim_all();
+        } else if pending < self.config.min_abandon_records() as usize
+            || self.cached_local_epoch != global_epoch
+        {
+            // the final record must be sealed with an epoch at least as recent as the one in
+            // which it was unlinked, otherwise it could be reclaimed by an adopting thread while
+            // other threads are still traversing it
+            self.reclaim_on_exit();
+        }
     }
 
     /// Retires the given `record` in the current epoch's bag queue as the final
@@ -115,11 +475,18 @@ impl LocalInner {
     ///
     /// # Safety
     ///
+    /// Must only be called after [`prepare_exit`][LocalInner::prepare_exit] and
+    /// after all records retired by the records reclaimed in the process have
+    /// been retired.
     /// After calling this method, no further calls to `retire_record` or
     /// `retire_final_record` must be made.
     #[cold]
     pub unsafe fn retire_final_record(&mut self, record: Retired) {
-        self.bags.retire_final_record(record);
+        #[cfg(feature = "testing")]
+        let record =
+            crate::sanitizer::sanitized_final(record, epoch_number(self.cached_local_epoch));
+
+        self.bags.get_or_insert_with(EpochBagQueues::new).retire_final_record(record);
     }
 
     /// Loads ([`Acquire`]) the global epoch and compares it with the local one.
@@ -161,6 +528,40 @@ impl LocalInner {
     /// This is annotated with `#[cold]` to keep it out of the fast path.
     #[cold]
     fn try_advance(&mut self, thread_state: &ThreadState, global_epoch: Epoch) {
+        if self.config.adaptive() {
+            self.adapt_to_pending();
+        }
+
+        // if the current thread is the only registered one, there are no other threads to visit;
+        // threads registering concurrently must start in the global epoch, so it is safe to
+        // advance directly
+        if THREADS.len() == 1 {
+            self.advance_count += 1;
+            if self.advance_count >= self.advance_threshold {
+                self.attempt_advance(global_epoch);
+            }
+
+            return;
+        }
+
+        if self.visit_next_thread(thread_state, global_epoch) {
+            self.advance_count += 1;
+
+            // we must have checked all other threads at least once, before we can attempt to
+            // advance the global epoch
+            if self.can_advance && self.advance_count >= self.advance_threshold {
+                self.attempt_advance(global_epoch);
+            }
+        }
+    }
+
+    /// Visits the next thread with the incremental thread iterator and returns
+    /// `true`, if the iterator could be advanced past the visited thread.
+    ///
+    /// Once the end of the list of threads is reached, the iterator is restarted
+    /// and the thread is marked as able to advance the global epoch.
+    #[inline]
+    fn visit_next_thread(&mut self, thread_state: &ThreadState, global_epoch: Epoch) -> bool {
         if let Ok(curr) = self.thread_iter.load_current_acquire() {
             let other = curr.unwrap_or_else(|| {
                 // we reached the end of the list and can restart, since this means we have
@@ -178,19 +579,92 @@ impl LocalInner {
             //   b) has announced the global epoch or
             //   c) is currently inactive
             if thread_state.is_same(other) || can_advance(global_epoch, other) {
-                self.advance_count += 1;
                 let _ = self.thread_iter.next();
+                return true;
+            }
+        }
+
+        false
+    }
 
-                // we must have checked all other threads at least once, before we can attempt to
-                // advance the global epoch
-                if self.can_advance && self.advance_count >= self.config.advance_threshold() {
-                    // (INN:4) this `Release` CAS synchronizes-with the `Acquire` load (INN:3)
-                    EPOCH.compare_and_swap(global_epoch, global_epoch + 1, Release);
-                }
+    /// Attempts to advance the global epoch and, in adaptive mode, raises the
+    /// thresholds if the attempt fails.
+    #[inline]
+    fn attempt_advance(&mut self, global_epoch: Epoch) {
+        if !advance_global_epoch(global_epoch) && self.config.adaptive() {
+            // another thread has advanced the epoch first, so back off to reduce the contention
+            let max_check = self.config.check_threshold().saturating_mul(ADAPTIVE_MAX_BACKOFF);
+            let max_advance = self.config.advance_threshold().saturating_mul(ADAPTIVE_MAX_BACKOFF);
+            self.check_threshold = cmp::min(self.check_threshold.saturating_mul(2), max_check);
+            self.advance_threshold =
+                cmp::min(cmp::max(self.advance_threshold.saturating_mul(2), 1), max_advance);
+        }
+    }
+
+    /// Scans all threads at once and attempts to advance the global epoch, if
+    /// none of them prevents it, then reclaims the oldest records, which have
+    /// become safe to reclaim.
+    ///
+    /// Unlike the incremental checks, the thread's own entry is not skipped,
+    /// since it may be active and may have announced an older epoch than the
+    /// one it has already observed.
+    #[cold]
+    fn enforce_budget(&mut self) {
+        let global_epoch = self.acquire_and_assess_global_epoch();
+        if THREADS.iter().all(|other| can_advance(global_epoch, other))
+            && advance_global_epoch(global_epoch)
+        {
+            self.acquire_and_assess_global_epoch();
+        }
+
+        if self.pending_rotations > 0 {
+            self.rotate_pending();
+        }
+    }
+
+    /// Adds `size` bytes to the global total of pending bytes, but first
+    /// attempts to reclaim the thread's own records for as long as the total
+    /// would otherwise exceed the global `budget`.
+    ///
+    /// A single record larger than the entire budget is admitted as soon as no
+    /// other records are pending.
+    #[cold]
+    fn reserve_global_bytes(&mut self, size: usize, budget: usize) {
+        let mut attempts = 0;
+        loop {
+            let pending = global::PENDING_BYTES.load(Relaxed);
+            let exceeded = pending > 0 && pending.saturating_add(size) > budget;
+            // only the thread's own records can be reclaimed, so it gives up on the budget if it
+            // has none or if they can not be reclaimed due to other active threads
+            if exceeded && attempts < GLOBAL_BUDGET_ATTEMPTS && self.pending_bytes() > 0 {
+                self.enforce_budget();
+                attempts += 1;
+                continue;
+            }
+
+            let reserved = pending + size;
+            if global::PENDING_BYTES
+                .compare_exchange_weak(pending, reserved, Relaxed, Relaxed)
+                .is_ok()
+            {
+                self.published_bytes += size;
+                return;
             }
         }
     }
 
+    /// Lowers the thresholds if the number of pending records has reached the
+    /// adaptive limit, so that the global epoch is advanced and the records are
+    /// reclaimed sooner.
+    #[cold]
+    fn adapt_to_pending(&mut self) {
+        let pending: usize = self.bag_counts.sorted().iter().sum();
+        if pending >= ADAPTIVE_PENDING_LIMIT {
+            self.check_threshold = cmp::max(self.check_threshold / 2, 1);
+            self.advance_threshold /= 2;
+        }
+    }
+
     /// Resets all incremental checks and advances the local epoch.
     ///
     /// # Safety
@@ -203,12 +677,114 @@ impl LocalInner {
     #[cold]
     unsafe fn advance_local_epoch(&mut self, global_epoch: Epoch) {
         self.cached_local_epoch = global_epoch;
+        self.reset_incremental_checks();
+
+        #[cfg(feature = "global-fifo")]
+        crate::fifo::reclaim(global_epoch);
+
+        if self.config.eager_rotation() {
+            self.rotate_and_reclaim();
+        } else {
+            // after three rotations, all bags have been reclaimed anyways
+            self.pending_rotations = cmp::min(self.pending_rotations + 1, BAG_QUEUE_COUNT as u32);
+        }
+    }
+
+    /// Resets all incremental checks for advancing the global epoch.
+    #[inline]
+    fn reset_incremental_checks(&mut self) {
         self.can_advance = false;
         self.check_count = 0;
         self.advance_count = 0;
         self.thread_iter = THREADS.iter();
+    }
+
+    /// Performs all bag rotations that have been deferred due to lazy rotation
+    /// and then attempts to adopt or reclaim any abandoned garbage.
+    ///
+    /// # Notes
+    ///
+    /// This is annotated with `#[cold]` to keep it out of the fast path.
+    #[cold]
+    fn rotate_pending(&mut self) {
+        for _ in 0..self.pending_rotations {
+            unsafe { self.rotate_bags() };
+        }
+
+        self.pending_rotations = 0;
+        unsafe { self.adopt_abandoned() };
+    }
+
+    /// Performs all deferred bag rotations and one additional rotation for every
+    /// global epoch advance since the last observed one, reclaiming all records
+    /// that have become safe to reclaim.
+    ///
+    /// Abandoned garbage is not adopted, since the thread is about to exit.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called by an exiting thread, which has already removed its
+    /// entry from the global thread list.
+    #[cold]
+    pub unsafe fn reclaim_on_exit(&mut self) {
+        // (INN:6) this `Acquire` load synchronizes-with the `Release` CAS (INN:4)
+        let global_epoch = EPOCH.load(Acquire);
+        let advances = match self.cached_local_epoch.relative_age(global_epoch) {
+            Ok(PossibleAge::SameEpoch) => 0,
+            Ok(PossibleAge::OneEpoch) => 1,
+            Ok(PossibleAge::TwoEpochs) => 2,
+            Err(_) => BAG_QUEUE_COUNT as u32,
+        };
+
+        // after three rotations, all bags have been reclaimed anyways
+        let rotations = cmp::min(self.pending_rotations + advances, BAG_QUEUE_COUNT as u32);
+        for _ in 0..rotations {
+            self.rotate_bags();
+        }
 
-        self.rotate_and_reclaim();
+        // the remaining records are sealed relative to the updated epoch when they are abandoned
+        self.cached_local_epoch = global_epoch;
+        self.pending_rotations = 0;
+    }
+
+    /// Reclaims all records of the last remaining registered thread.
+    ///
+    /// Since no other thread is registered, none can be accessing any of the
+    /// records, which would otherwise be abandoned and never be adopted again
+    /// (e.g. when the main thread of a single-threaded program exits).
+    /// Threads registering concurrently can only reach records that have not
+    /// yet been unlinked, so these are likewise unaffected.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called by an exiting thread, which has already removed its
+    /// entry from the global thread list, and only if the list is empty.
+    #[cold]
+    unsafe fn reclaim_all(&mut self) {
+        for _ in 0..BAG_QUEUE_COUNT {
+            self.rotate_bags();
+        }
+
+        // (INN:8) this `Acquire` load synchronizes-with the `Release` CAS (INN:4)
+        self.cached_local_epoch = EPOCH.load(Acquire);
+        self.pending_rotations = 0;
+    }
+
+    /// Unconditionally reclaims all records in the thread's epoch bag queues and
+    /// all abandoned records of exited threads.
+    ///
+    /// # Safety
+    ///
+    /// No other thread must be active or still be able to access any retired
+    /// record.
+    #[cold]
+    pub unsafe fn reclaim_all_now(&mut self) {
+        self.reclaim_all();
+        // the abandoned records include the thread list entries of exited threads, which the
+        // thread's own iterator may still point to
+        self.reset_incremental_checks();
+        // sealed bags reclaim all of their records when they are dropped
+        global::take_every_abandoned().for_each(mem::drop);
     }
 
     /// Retires records from the oldest epoch queue, rotates the queues and then
@@ -220,18 +796,132 @@ impl LocalInner {
     /// The global epoch must be ahead of the local epoch.
     #[inline]
     unsafe fn rotate_and_reclaim(&mut self) {
+        #[cfg(feature = "chaos")]
+        crate::chaos::maybe_yield();
+
         // reclaims the oldest retired records and rotates the queues so that further records are
         // retired into the flushed queue
-        self.bags.rotate_and_reclaim(&mut self.bag_pool);
+        self.rotate_bags();
+
+        #[cfg(feature = "chaos")]
+        crate::chaos::maybe_yield();
+
+        self.adopt_abandoned();
+    }
+
+    /// Reclaims the records in the oldest epoch bag queue, rotates the queues
+    /// and returns the number of reclaimed records.
+    ///
+    /// Threads that have never retired any records have no bags to rotate.
+    ///
+    /// # Safety
+    ///
+    /// The global epoch must be ahead of the epoch in which the records in the
+    /// oldest queue were retired by at least two.
+    #[inline]
+    unsafe fn rotate_bags(&mut self) -> usize {
+        if let Some(bags) = self.bags.as_mut() {
+            bags.rotate_and_reclaim(&mut self.bag_pool);
+        }
+
+        #[cfg(all(debug_assertions, not(feature = "global-fifo")))]
+        self.recent_retires.rotate();
+
+        let reclaimed = self.bag_counts.rotate();
+        if self.config.global_memory_budget() > 0 {
+            let pending = self.bag_counts.pending_bytes();
+            global::PENDING_BYTES.fetch_sub(self.published_bytes - pending, Relaxed);
+            self.published_bytes = pending;
+        }
+
+        reclaimed
+    }
+
+    /// Attempts to adopt or reclaim any abandoned garbage which remains from
+    /// exited threads.
+    ///
+    /// Adoption is deferred to less loaded threads, if the thread's number of
+    /// pending records is above the average of all threads.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called after all epoch bag rotations for the current local
+    /// epoch have been performed.
+    #[inline]
+    unsafe fn adopt_abandoned(&mut self) {
+        // the average serves as an approximation of the median, which would require access to the
+        // pending records of all threads
+        let pending = self.publish_pending();
+        let threads = THREADS.len();
+        if threads > 1 && pending.saturating_mul(threads) > global::PENDING.load(Relaxed) {
+            return;
+        }
 
         // after rotating the epoch bags, we can potentially insert abandoned bags into their
         // appropriate queues (this must only be done AFTER the rotation!)
-        for sealed in ABANDONED.take_all() {
+        self.adopt_sealed(global::take_abandoned());
+    }
+
+    /// Publishes the current number of pending records to the global total and
+    /// returns it.
+    #[inline]
+    fn publish_pending(&mut self) -> usize {
+        let pending: usize = self.bag_counts.sorted().iter().sum();
+        if pending > self.published_pending {
+            global::PENDING.fetch_add(pending - self.published_pending, Relaxed);
+        } else if pending < self.published_pending {
+            global::PENDING.fetch_sub(self.published_pending - pending, Relaxed);
+        }
+
+        self.published_pending = pending;
+        pending
+    }
+
+    /// Adopts all pending records of the `other` thread-local state, which
+    /// belongs to a thread that is handing off its reclamation responsibility.
+    ///
+    /// # Safety
+    ///
+    /// The thread owning `other` must have already removed its entry from the
+    /// global thread list and must have retired it as its final record after
+    /// calling `reclaim_on_exit`.
+    #[cold]
+    pub unsafe fn adopt_local(&mut self, mut other: LocalInner) {
+        // the records must be adopted relative to an epoch at least as recent as the one they
+        // were sealed with
+        self.acquire_and_assess_global_epoch();
+        if self.pending_rotations > 0 {
+            self.rotate_pending();
+        }
+
+        let bags = match other.bags.take() {
+            Some(bags) => bags,
+            None => return,
+        };
+
+        let records = other.bag_counts.own_sorted();
+        if let Some(sealed) = SealedList::from_bags(bags, other.cached_local_epoch, records) {
+            self.adopt_sealed(AbandonedIter::from(sealed));
+        }
+    }
+
+    /// Retires all `sealed` bag queues into the epoch bag queues matching their
+    /// seals.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called after all epoch bag rotations for the current local
+    /// epoch have been performed.
+    #[inline]
+    unsafe fn adopt_sealed(&mut self, sealed: impl Iterator<Item = Box<Sealed>>) {
+        for sealed in sealed {
             // sealed bags are retired according to the already adjusted epoch, otherwise they
             // are dropped and their contents reclaimed right away
             if let Ok(age) = sealed.seal.relative_age(self.cached_local_epoch) {
-                let retired = Retired::new_unchecked(NonNull::from(Box::leak(sealed)));
-                self.bags.retire_record_by_age(retired, age, &mut self.bag_pool);
+                let retired = Retired::from_box(sealed);
+                self.bag_counts.retire_by_age(age);
+                let bags = self.bags.get_or_insert_with(EpochBagQueues::new);
+                bags.retire_record_by_age(retired, age, &mut self.bag_pool);
             }
         }
     }
@@ -245,15 +935,45 @@ impl Drop for LocalInner {
     // where other threads can adopt them and integrate them into their own appropriate epoch bags.
     #[cold]
     fn drop(&mut self) {
-        let bags = unsafe { ptr::read(&*self.bags) };
-        if let Some(sealed) = SealedList::from_bags(bags, self.cached_local_epoch) {
-            ABANDONED.push(sealed);
+        // the abandoned records no longer count towards the load of this thread
+        global::PENDING.fetch_sub(self.published_pending, Relaxed);
+        global::PENDING_BYTES.fetch_sub(self.published_bytes, Relaxed);
+        let bags = match self.bags.take() {
+            Some(bags) => bags,
+            None => return,
+        };
+
+        let records = self.bag_counts.own_sorted();
+        if let Some(sealed) = SealedList::from_bags(bags, self.cached_local_epoch, records) {
+            global::push_abandoned(sealed);
         }
     }
 }
 
 /***** helper functions ***************************************************************************/
 
+/// Attempts to advance the global epoch by one and returns `true` if
+/// successful.
+#[inline]
+fn advance_global_epoch(global_epoch: Epoch) -> bool {
+    #[cfg(feature = "chaos")]
+    crate::chaos::maybe_yield();
+
+    // (INN:4) this `Release` CAS synchronizes-with the `Acquire` loads (INN:3), (INN:6),
+    // (INN:7), (INN:8) and the `SeqCst` load (INN:9)
+    let advanced = EPOCH.compare_and_swap(global_epoch, global_epoch + 1, Release) == global_epoch;
+    if advanced {
+        #[cfg(any(test, feature = "std"))]
+        crate::time::record_advance();
+        global::notify_advance(global_epoch);
+    } else {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_advance_cas_loss();
+    }
+
+    advanced
+}
+
 /// A visiting thread can advance its local thread iterator if the visited
 /// thread is either inactive or has itself announced the global epoch.
 #[inline(always)]
@@ -263,3 +983,70 @@ fn can_advance(global_epoch: Epoch, other: &ThreadState) -> bool {
     let (epoch, state) = other.load(SeqCst);
     epoch == global_epoch || state == Inactive
 }
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::NonNull;
+    use core::sync::atomic::Ordering::SeqCst;
+
+    use debra_common::LocalAccess;
+
+    use crate::config::{Config, ConfigBuilder};
+    use crate::global::EPOCH;
+    use crate::local::Local;
+    use crate::Retired;
+
+    #[test]
+    fn with_config() {
+        let config = ConfigBuilder::new().check_threshold(7).advance_threshold(3).build();
+        let local = Local::with_config(config);
+        let inner = unsafe { &*local.inner.get() };
+        assert_eq!((inner.check_threshold, inner.advance_threshold), (7, 3));
+
+        // the override does not affect any other threads
+        let default = Local::new();
+        let inner = unsafe { &*default.inner.get() };
+        let global = crate::CONFIG.try_get().copied().unwrap_or_else(Config::default);
+        assert_eq!(inner.check_threshold, global.check_threshold());
+    }
+
+    fn bursty_workload(local: &Local) -> usize {
+        for _ in 0..1_000 {
+            local.set_active();
+            for _ in 0..100 {
+                let record = NonNull::from(Box::leak(Box::new(0usize)));
+                local.retire_record(unsafe { Retired::new_unchecked(record) });
+            }
+            local.set_inactive();
+        }
+
+        local.bag_balance().iter().sum()
+    }
+
+    #[test]
+    fn adaptive_thresholds() {
+        const TOTAL: usize = 100_000;
+        let config = ConfigBuilder::new().check_threshold(100).advance_threshold(100);
+
+        // with fixed thresholds, the thread would only attempt to advance the global epoch after
+        // 10_000 bursts, so all of its records remain pending unless other threads advance it
+        let fixed = Local::with_config(config.build());
+        let _ = bursty_workload(&fixed);
+        let inner = unsafe { &*fixed.inner.get() };
+        assert_eq!((inner.check_threshold, inner.advance_threshold), (100, 100));
+
+        // in adaptive mode, the thresholds are lowered as the records accumulate
+        let adaptive = Local::with_config(config.adaptive(true).build());
+        let pending = bursty_workload(&adaptive);
+        let inner = unsafe { &*adaptive.inner.get() };
+        assert!(inner.check_threshold < 100);
+        assert!(pending < TOTAL / 2, "{} of {} records still pending", pending, TOTAL);
+    }
+
+    #[test]
+    fn advance_stale_epoch() {
+        // the global epoch is never rewound, so advancing from an outdated value must fail
+        let global_epoch = EPOCH.load(SeqCst);
+        assert!(!super::advance_global_epoch(global_epoch - 1));
+    }
+}
@@ -1,18 +1,30 @@
 //! Thread local state
 
+mod counts;
+#[cfg(not(feature = "std"))]
+mod handle;
 mod inner;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use core::cell::{Cell, UnsafeCell};
 use core::mem::ManuallyDrop;
 use core::ptr;
 use core::sync::atomic::Ordering;
 
+use debra_common::epoch::Epoch;
 use debra_common::thread::ThreadState;
 use debra_common::LocalAccess;
 
+use crate::config::Config;
 use crate::global::{EPOCH, THREADS};
 use crate::{Debra, Retired};
 
+pub(crate) use self::counts::BAG_QUEUE_COUNT;
+#[cfg(not(feature = "std"))]
+pub use self::handle::LocalHandle;
+
 use self::inner::LocalInner;
 
 type ThreadEntry = crate::list::ListEntry<'static, ThreadState>;
@@ -26,6 +38,9 @@ type ThreadEntry = crate::list::ListEntry<'static, ThreadState>;
 pub struct Local {
     state: ManuallyDrop<ThreadEntry>,
     guard_count: Cell<usize>,
+    scan_left: Cell<bool>,
+    reclaiming: Cell<bool>,
+    reentrant: UnsafeCell<Vec<(Retired, usize)>>,
     inner: UnsafeCell<LocalInner>,
 }
 
@@ -33,7 +48,26 @@ pub struct Local {
 
 impl Local {
     /// Creates and globally registers a new [`Local`].
+    ///
+    /// The [`Local`] uses a copy of the global [`CONFIG`][crate::CONFIG] or the
+    /// default configuration, if none has been set.
     pub fn new() -> Self {
+        Self::with_inner_state(LocalInner::new)
+    }
+
+    /// Creates and globally registers a new [`Local`], which uses the given
+    /// `config` instead of the global [`CONFIG`][crate::CONFIG].
+    ///
+    /// This allows e.g. tuning the threads of different worker pools
+    /// differently.
+    pub fn with_config(config: Config) -> Self {
+        Self::with_inner_state(|global_epoch| LocalInner::with_config(global_epoch, config))
+    }
+
+    /// Creates and globally registers a new [`Local`] with the inner state
+    /// created by `inner` from the current global epoch.
+    #[inline]
+    fn with_inner_state(inner: impl FnOnce(Epoch) -> LocalInner) -> Self {
         let global_epoch = EPOCH.load(Ordering::SeqCst);
         let thread_epoch = ThreadState::new(global_epoch);
         let state = THREADS.insert(thread_epoch);
@@ -41,14 +75,367 @@ impl Local {
         Self {
             state: ManuallyDrop::new(state),
             guard_count: Cell::default(),
-            inner: UnsafeCell::new(LocalInner::new(global_epoch)),
+            scan_left: Cell::default(),
+            reclaiming: Cell::default(),
+            reentrant: UnsafeCell::default(),
+            inner: UnsafeCell::new(inner(global_epoch)),
         }
     }
 
+    /// Unconditionally reclaims all records retired by the thread and all
+    /// abandoned records of exited threads, regardless of the global epoch,
+    /// e.g. for a clean shutdown.
+    ///
+    /// Records retired by the destructors of reclaimed records are reclaimed
+    /// as well.
+    ///
+    /// # Safety
+    ///
+    /// The thread must not be active and no other thread must be active or
+    /// still be able to access any retired record, e.g. because all other
+    /// threads have been joined.
+    #[inline]
+    pub unsafe fn reclaim_all_now(&self) {
+        debug_assert_eq!(self.guard_count.get(), 0, "`reclaim_all_now` called with a live guard");
+        loop {
+            // records retired re-entrantly are only retired once the current pass is complete
+            self.with_inner(|inner| inner.reclaim_all_now());
+            if self.bag_balance().iter().all(|&count| count == 0) {
+                return;
+            }
+        }
+    }
+
+    /// Returns the number of live guards of the thread, including nested
+    /// guards and clones.
+    #[inline]
+    pub fn guard_count(&self) -> usize {
+        self.guard_count.get()
+    }
+
     /// Attempts to reclaim the retired records in the oldest epoch bag queue.
+    ///
+    /// This has no effect while the thread is active (i.e. has at least one
+    /// live guard), since the thread's announced epoch and state must not be
+    /// altered for as long as any guard exists.
     #[inline]
     pub fn try_flush(&self) {
-        unsafe { &mut *self.inner.get() }.try_flush(&**self.state);
+        if self.guard_count.get() == 0 {
+            self.with_inner(|inner| inner.try_flush(&**self.state));
+        }
+    }
+
+    /// Marks the end of a batch of operations (e.g. a logical transaction), so
+    /// that the next incremental check for advancing the global epoch is not
+    /// deferred until the configured check threshold is reached.
+    ///
+    /// If the thread is currently active, the check is performed immediately,
+    /// otherwise when the thread next becomes active.
+    #[inline]
+    pub fn checkpoint(&self) {
+        let active = self.guard_count.get() > 0;
+        self.with_inner(|inner| inner.checkpoint(&**self.state, active));
+    }
+
+    /// Attempts to advance the global epoch once, visiting all registered
+    /// threads, and returns `true` if the epoch has been advanced.
+    ///
+    /// The epoch is only advanced, if all other threads are either inactive or
+    /// have announced the current global epoch, i.e. this never advances the
+    /// epoch prematurely.
+    /// This has no effect and returns `false` while the thread is active.
+    #[inline]
+    pub fn try_advance_epoch(&self) -> bool {
+        match self.guard_count.get() {
+            0 => self.with_inner(|inner| inner.try_advance_epoch(&**self.state)),
+            _ => false,
+        }
+    }
+
+    /// Returns the number of records currently cached in each of the thread's
+    /// epoch bag queues, starting with the queue of the current epoch.
+    ///
+    /// Adopted bags of exited threads are counted as a single record each.
+    #[inline]
+    pub fn bag_balance(&self) -> [usize; 3] {
+        unsafe { &*self.inner.get() }.bag_counts()
+    }
+
+    /// Returns the epoch and the number of records of each of the thread's
+    /// epoch bag queues, starting with the queue of the current epoch.
+    ///
+    /// Epochs are given as the number of global epoch advances, the records in
+    /// a queue were retired in the respective epoch.
+    /// Non-empty queues with epochs far behind the global epoch indicate that
+    /// the thread has not rotated its queues in a while.
+    #[inline]
+    pub fn pending_by_epoch(&self) -> [(u64, usize); 3] {
+        unsafe { &*self.inner.get() }.pending_by_epoch()
+    }
+
+    /// Returns `true` if the largest epoch bag queue contains more than `ratio`
+    /// times the average number of records per queue.
+    ///
+    /// An imbalance indicates a bursty retirement pattern, which causes uneven
+    /// pauses when the respective queue is eventually reclaimed.
+    #[inline]
+    pub fn is_imbalanced(&self, ratio: f64) -> bool {
+        let balance = self.bag_balance();
+        let total: usize = balance.iter().sum();
+        let max = balance.iter().copied().max().unwrap_or(0);
+        total > 0 && max as f64 > ratio * (total as f64 / balance.len() as f64)
+    }
+
+    /// Reclaims the records retired in the given `epoch` and returns their
+    /// number.
+    ///
+    /// Epochs are given as the number of global epoch advances, as returned by
+    /// [`pending_by_epoch`][Local::pending_by_epoch].
+    /// Since a thread may retire records while its local epoch lags behind the
+    /// global epoch by one, the records of `epoch` are only safe to reclaim
+    /// once the global epoch has reached `epoch + 3`.
+    /// Any records retired in earlier epochs are reclaimed as well (but not
+    /// counted), while records retired in later epochs remain pending.
+    ///
+    /// Returns 0 without reclaiming anything, if the records are not yet safe
+    /// to reclaim, if there are no pending records for `epoch` or if the thread
+    /// is currently active.
+    #[inline]
+    pub fn reclaim_epoch(&self, epoch: u64) -> usize {
+        match self.guard_count.get() {
+            0 => self.with_inner(|inner| inner.reclaim_epoch(&**self.state, epoch)),
+            _ => 0,
+        }
+    }
+
+    /// Returns the total size in bytes of all records currently cached in the
+    /// thread's epoch bag queues.
+    ///
+    /// Only records retired with a known size are accounted for, i.e. neither
+    /// records retired through [`LocalAccess::retire_record`] nor adopted bags
+    /// of exited threads.
+    #[inline]
+    pub fn pending_bytes(&self) -> usize {
+        unsafe { &*self.inner.get() }.pending_bytes()
+    }
+
+    /// Returns the index of the epoch bag queue, in which records are currently
+    /// retired.
+    ///
+    /// The index advances by one (modulo 3) with each rotation of the queues.
+    /// This is only intended for white-box testing of the rotation logic.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn current_bag_index(&self) -> usize {
+        unsafe { &*self.inner.get() }.current_bag_index()
+    }
+
+    /// Returns the number of rotations of the thread's epoch bag queues, which
+    /// have been performed so far.
+    ///
+    /// Deferred rotations (see [`ConfigBuilder::eager_rotation`][crate::ConfigBuilder::eager_rotation])
+    /// are only counted once they are actually performed.
+    #[cfg(feature = "testing")]
+    #[inline]
+    pub fn rotations_performed(&self) -> u64 {
+        unsafe { &*self.inner.get() }.rotations_performed()
+    }
+
+    /// Retires the given `record`, which has a size of `size` bytes, for the
+    /// purpose of enforcing the configured byte budget.
+    #[inline]
+    pub(crate) fn retire_sized(&self, record: Retired, size: usize) {
+        // records retired by the destructor of a record, which is being reclaimed by this thread
+        if self.reclaiming.get() {
+            unsafe { &mut *self.reentrant.get() }.push((record, size));
+            return;
+        }
+
+        self.with_inner(|inner| {
+            #[cfg(debug_assertions)]
+            {
+                if self.guard_count.get() > 0 {
+                    inner.record_guard_op();
+                    inner.record_active_retire();
+                }
+            }
+
+            inner.retire_record(record, size);
+        });
+    }
+
+    /// Retires all given `records` at once.
+    ///
+    /// The thread local state is borrowed only once for the entire batch,
+    /// which amortizes the cost of retiring many records unlinked together,
+    /// e.g. when compacting a data structure.
+    #[inline]
+    pub fn retire_batch(&self, records: impl IntoIterator<Item = Retired>) {
+        self.retire_sized_batch(records.into_iter().map(|record| (record, 0)));
+    }
+
+    /// Retires all given `records` at once, each with its size in bytes for
+    /// the purpose of enforcing the configured byte budget.
+    #[inline]
+    pub(crate) fn retire_sized_batch(&self, records: impl IntoIterator<Item = (Retired, usize)>) {
+        // records retired by the destructor of a record, which is being reclaimed by this thread
+        if self.reclaiming.get() {
+            unsafe { &mut *self.reentrant.get() }.extend(records);
+            return;
+        }
+
+        self.with_inner(|inner| {
+            for (record, size) in records {
+                #[cfg(debug_assertions)]
+                {
+                    if self.guard_count.get() > 0 {
+                        inner.record_guard_op();
+                        inner.record_active_retire();
+                    }
+                }
+
+                inner.retire_record(record, size);
+            }
+        });
+    }
+
+    /// Transfers all pending records to `other` and de-registers the thread.
+    ///
+    /// The records are merged into the epoch bag queues of `other` according
+    /// to the epochs they were retired in.
+    /// This is more efficient than dropping `self` and having the records
+    /// abandoned and adopted through the global queue, if the thread owning
+    /// `other` is known to take over the workload of the current thread.
+    #[inline]
+    pub fn transfer_to(self, other: &Local) {
+        debug_assert_eq!(self.guard_count.get(), 0, "transferring `Local` with live guards");
+        let local = ManuallyDrop::new(self);
+
+        // remove thread entry from list and retire as last record, like when dropping
+        let state = unsafe { ptr::read(&*local.state) };
+        let entry = THREADS.remove(state);
+
+        unsafe {
+            local.reclaim_before_exit(|inner| {
+                inner.reclaim_on_exit();
+                inner.prepare_exit();
+            });
+            local.with_inner(|inner| inner.retire_final_record(Retired::new_unchecked(entry)));
+
+            // the worklist has been drained completely but still owns its allocation
+            ptr::drop_in_place(local.reentrant.get());
+            let inner = ptr::read(local.inner.get());
+            other.with_inner(|other| other.adopt_local(inner));
+        }
+    }
+
+    /// Temporarily removes the thread from the scans of all other threads, so
+    /// that it can no longer prevent the global epoch from being advanced.
+    ///
+    /// The thread is announced as inactive, even if it currently has live
+    /// guards, and remains so until [`rejoin_scan`][Local::rejoin_scan] is
+    /// called.
+    /// Creating further nested guards in the meantime does not alter this
+    /// state, but creating a new outermost guard implicitly rejoins the scans.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that no references obtained through any of the
+    /// thread's currently live guards are dereferenced until the thread has
+    /// rejoined the scans, since the records they point to may be reclaimed
+    /// in the meantime.
+    #[inline]
+    pub unsafe fn leave_scan(&self) {
+        if !self.scan_left.replace(true) {
+            let inner = &*self.inner.get();
+            inner.set_inactive(&**self.state);
+        }
+    }
+
+    /// Re-includes the thread in the scans of all other threads after a
+    /// previous call to [`leave_scan`][Local::leave_scan].
+    ///
+    /// If the thread has any live guards, it announces the current global
+    /// epoch and is marked as active again.
+    #[inline]
+    pub fn rejoin_scan(&self) {
+        if self.scan_left.replace(false) && self.guard_count.get() > 0 {
+            self.with_inner(|inner| inner.set_active(&**self.state));
+        }
+    }
+
+    /// Calls `f` with exclusive access to the inner state, which may reclaim
+    /// records.
+    ///
+    /// Any records retired by the destructors of the reclaimed records must
+    /// not access the inner state re-entrantly, so they are collected in a
+    /// worklist and only retired once `f` has returned.
+    /// This also prevents any unbounded recursion for recursive structures
+    /// and ensures that re-entrantly retired records are never reclaimed in
+    /// the same pass.
+    #[inline]
+    fn with_inner<R>(&self, f: impl FnOnce(&mut LocalInner) -> R) -> R {
+        self.reclaiming.set(true);
+        let res = f(unsafe { &mut *self.inner.get() });
+        self.reclaiming.set(false);
+
+        if !unsafe { &*self.reentrant.get() }.is_empty() {
+            self.retire_reentrant(true);
+        }
+
+        res
+    }
+
+    /// Retires the records in the worklist of re-entrantly retired records.
+    ///
+    /// Retiring may itself trigger the reclamation of further records, so the
+    /// worklist is processed iteratively.
+    /// If `bounded` is `true`, only as many records are retired as permitted
+    /// by the configured limit and cap and the remaining ones are deferred,
+    /// otherwise the worklist is processed until it remains empty.
+    #[cold]
+    fn retire_reentrant(&self, bounded: bool) {
+        let mut retired = 0;
+        loop {
+            let inner = unsafe { &mut *self.inner.get() };
+            if bounded && !inner.accepts_reentrant(retired) {
+                return;
+            }
+
+            let (record, size) = match unsafe { &mut *self.reentrant.get() }.pop() {
+                Some(record) => record,
+                None => return,
+            };
+
+            self.reclaiming.set(true);
+            inner.retire_record(record, size);
+            self.reclaiming.set(false);
+            retired += 1;
+        }
+    }
+
+    /// Calls `reclaim` with exclusive access to the inner state of the exiting
+    /// thread and retires all records retired re-entrantly in the process,
+    /// repeating this until no further records are retired.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called by an exiting thread, which has already removed its
+    /// entry from the global thread list.
+    #[cold]
+    unsafe fn reclaim_before_exit(&self, reclaim: impl Fn(&mut LocalInner)) {
+        loop {
+            self.reclaiming.set(true);
+            reclaim(&mut *self.inner.get());
+            self.reclaiming.set(false);
+
+            // all records must be retired before the final record, regardless of the limit
+            if (&*self.reentrant.get()).is_empty() {
+                return;
+            }
+
+            self.retire_reentrant(false);
+        }
     }
 }
 
@@ -70,8 +457,20 @@ impl<'a> LocalAccess for &'a Local {
         self.guard_count.set(count + 1);
 
         if count == 0 {
-            let inner = unsafe { &mut *self.inner.get() };
-            inner.set_active(&**self.state);
+            self.scan_left.set(false);
+            self.with_inner(|inner| {
+                // the thread remains counted as active until its outermost guard is dropped, even
+                // if it leaves and rejoins the scans in the meantime
+                inner.enter_active();
+                inner.set_active(&**self.state);
+            });
+        } else {
+            #[cfg(debug_assertions)]
+            {
+                if !self.reclaiming.get() {
+                    unsafe { &mut *self.inner.get() }.record_guard_op();
+                }
+            }
         }
     }
 
@@ -82,6 +481,9 @@ impl<'a> LocalAccess for &'a Local {
         if count == 1 {
             let inner = unsafe { &*self.inner.get() };
             inner.set_inactive(&**self.state);
+            if inner.leave_active() {
+                self.with_inner(|inner| inner.advance_quiescent(&**self.state));
+            }
         } else if count == 0 {
             panic!("guard count overflow");
         }
@@ -89,8 +491,7 @@ impl<'a> LocalAccess for &'a Local {
 
     #[inline]
     fn retire_record(self, record: Retired) {
-        let inner = unsafe { &mut *self.inner.get() };
-        inner.retire_record(record);
+        self.retire_sized(record, 0);
     }
 }
 
@@ -113,9 +514,142 @@ impl Drop for Local {
         let entry = THREADS.remove(state);
 
         unsafe {
-            let retired = Retired::new_unchecked(entry);
-            let inner = &mut *self.inner.get();
-            inner.retire_final_record(retired);
+            self.reclaim_before_exit(|inner| inner.prepare_exit());
+            self.with_inner(|inner| inner.retire_final_record(Retired::new_unchecked(entry)));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::NonNull;
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering::{Relaxed, SeqCst},
+    };
+    use std::thread;
+
+    use debra_common::LocalAccess;
+
+    use crate::global::{epoch_number, EPOCH};
+    use crate::Retired;
+
+    use super::Local;
+
+    struct DropCount<'a>(&'a AtomicUsize);
+    impl Drop for DropCount<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Relaxed);
+        }
+    }
+
+    /// Advances the global epoch at least `advances` times through the regular
+    /// checks of the given `driver`, so that no records retired by any
+    /// concurrently running tests are reclaimed prematurely.
+    fn advance_epoch(driver: &Local, advances: u64) {
+        let start = epoch_number(EPOCH.load(SeqCst));
+        while epoch_number(EPOCH.load(SeqCst)).wrapping_sub(start) < advances {
+            if !driver.try_advance_epoch() {
+                thread::yield_now();
+            }
+        }
+    }
+
+    fn retire_many(local: &Local, count: usize) {
+        for _ in 0..count {
+            let record = NonNull::from(Box::leak(Box::new(0usize)));
+            local.retire_record(unsafe { Retired::new_unchecked(record) });
+        }
+    }
+
+    #[test]
+    fn bag_balance() {
+        let local = Local::new();
+        retire_many(&local, 1_000);
+        assert_eq!(local.bag_balance()[0], 1_000);
+
+        // advance the global epoch once and let the thread observe it
+        advance_epoch(&local, 1);
+        local.try_flush();
+
+        retire_many(&local, 10);
+        let balance = local.bag_balance();
+        assert!(balance[0] >= 10);
+        assert!(balance[1] >= 1_000);
+        assert!(local.is_imbalanced(2.0));
+    }
+
+    #[test]
+    fn pending_by_epoch() {
+        let local = Local::new();
+        retire_many(&local, 5);
+        let [(epoch, count), _, _] = local.pending_by_epoch();
+        assert_eq!(count, 5);
+
+        // advance the global epoch once and let the thread observe it
+        advance_epoch(&local, 1);
+        local.try_flush();
+
+        retire_many(&local, 3);
+        let pending = local.pending_by_epoch();
+        assert!(pending[0].0 > epoch);
+        assert_eq!(pending[0].1, 3);
+        assert_eq!(pending[1].0, pending[0].0 - 1);
+        assert_eq!(pending[2].0, pending[0].0 - 2);
+
+        // the earlier records remain pending under their original epoch, unless
+        // the epoch has been advanced more than once concurrently by other tests
+        if pending[1].0 == epoch {
+            assert_eq!(pending[1].1, 5);
+        }
+    }
+
+    #[test]
+    fn transfer_to() {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let source = Local::new();
+        let target = Local::new();
+        for _ in 0..10 {
+            let record = NonNull::from(Box::leak(Box::new(DropCount(&COUNTER))));
+            source.retire_record(unsafe { Retired::new_unchecked(record) });
+        }
+
+        source.transfer_to(&target);
+        assert_eq!(COUNTER.load(Relaxed), 0);
+        assert!(target.bag_balance().iter().sum::<usize>() > 0);
+
+        // the transferred records are reclaimed once the target has rotated its bags often enough
+        for _ in 0..100 {
+            advance_epoch(&target, 1);
+            target.try_flush();
+
+            if COUNTER.load(Relaxed) == 10 {
+                return;
+            }
+        }
+
+        panic!("transferred records were not reclaimed");
+    }
+
+    #[test]
+    fn reclaim_epoch() {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let local = Local::new();
+        for _ in 0..5 {
+            let record = NonNull::from(Box::leak(Box::new(DropCount(&COUNTER))));
+            local.retire_record(unsafe { Retired::new_unchecked(record) });
+        }
+
+        let [(epoch, count), _, _] = local.pending_by_epoch();
+        assert_eq!(count, 5);
+
+        // advance the global epoch three times without letting the thread observe it
+        advance_epoch(&Local::new(), 3);
+
+        assert_eq!(local.reclaim_epoch(epoch), 5);
+        assert_eq!(COUNTER.load(Relaxed), 5);
+        assert_eq!(local.reclaim_epoch(epoch), 0);
+    }
+}
@@ -0,0 +1,64 @@
+//! Counters for diagnosing contention, which are only compiled with the
+//! `metrics` feature.
+
+use core::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+/// The number of failed CAS attempts when inserting into the thread list.
+static LIST_INSERT_RETRIES: AtomicU64 = AtomicU64::new(0);
+/// The number of removals from the thread list requiring a repeated unlink.
+static LIST_REPEAT_REMOVES: AtomicU64 = AtomicU64::new(0);
+/// The number of attempts to advance the global epoch lost to other threads.
+static ADVANCE_CAS_LOSSES: AtomicU64 = AtomicU64::new(0);
+
+/// Counts a failed CAS when inserting into the thread list.
+#[inline]
+pub(crate) fn record_list_insert_retry() {
+    LIST_INSERT_RETRIES.fetch_add(1, Relaxed);
+}
+
+/// Counts a removal from the thread list requiring a repeated unlink.
+#[inline]
+pub(crate) fn record_list_repeat_remove() {
+    LIST_REPEAT_REMOVES.fetch_add(1, Relaxed);
+}
+
+/// Counts an attempt to advance the global epoch lost to another thread.
+#[inline]
+pub(crate) fn record_advance_cas_loss() {
+    ADVANCE_CAS_LOSSES.fetch_add(1, Relaxed);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// ContentionMetrics
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A snapshot of the global contention counters.
+///
+/// High numbers of list insert retries indicate contention during thread
+/// registration, while high numbers of lost epoch advances indicate overly
+/// eager check and advance thresholds.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ContentionMetrics {
+    /// The number of failed CAS attempts when registering new threads.
+    pub list_insert_retries: u64,
+    /// The number of thread de-registrations, which required repeated
+    /// attempts to unlink the thread's entry.
+    pub list_repeat_removes: u64,
+    /// The number of attempts to advance the global epoch, which were lost to
+    /// other threads advancing it first.
+    pub advance_cas_losses: u64,
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl ContentionMetrics {
+    /// Takes a snapshot of the current global counters.
+    #[inline]
+    pub(crate) fn load() -> Self {
+        Self {
+            list_insert_retries: LIST_INSERT_RETRIES.load(Relaxed),
+            list_repeat_removes: LIST_REPEAT_REMOVES.load(Relaxed),
+            advance_cas_losses: ADVANCE_CAS_LOSSES.load(Relaxed),
+        }
+    }
+}
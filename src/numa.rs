@@ -0,0 +1,170 @@
+//! NUMA-aware routing of abandoned bag queues.
+//!
+//! Instead of a single global queue, abandoned bags are pushed into one queue
+//! per NUMA node, which is determined by calling the function stored in
+//! [`NUMA_NODE`].
+//! Threads adopting abandoned bags prefer the queue of their own node and only
+//! fall back to the queues of other nodes if their own is empty.
+
+#[cfg(feature = "std")]
+use conquer_once::spin::OnceCell;
+#[cfg(not(feature = "std"))]
+use conquer_once::OnceCell;
+
+use crate::abandoned::{AbandonedQueue, Iter};
+use crate::sealed::SealedList;
+
+/// The maximum number of distinct NUMA nodes, higher node ids are mapped onto
+/// the available queues.
+pub(crate) const MAX_NUMA_NODES: usize = 8;
+
+/// Global function for determining the NUMA node of the calling thread.
+///
+/// Since the detection is platform-specific, it has to be supplied by the user.
+/// If it is not set, all threads are considered to be running on node 0.
+pub static NUMA_NODE: OnceCell<fn() -> usize> = OnceCell::new();
+
+/// Returns the NUMA node of the calling thread.
+#[inline]
+pub(crate) fn current_node() -> usize {
+    NUMA_NODE.try_get().copied().map(|node| node()).unwrap_or(0)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// NumaQueues
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A set of [`AbandonedQueue`]s, one for each NUMA node.
+#[derive(Debug)]
+pub(crate) struct NumaQueues {
+    queues: [AbandonedQueue; MAX_NUMA_NODES],
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl NumaQueues {
+    /// Creates a new set of empty queues.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            queues: [
+                AbandonedQueue::new(),
+                AbandonedQueue::new(),
+                AbandonedQueue::new(),
+                AbandonedQueue::new(),
+                AbandonedQueue::new(),
+                AbandonedQueue::new(),
+                AbandonedQueue::new(),
+                AbandonedQueue::new(),
+            ],
+        }
+    }
+
+    /// Pushes `sealed` to the queue of the given NUMA `node`.
+    #[inline]
+    pub fn push(&self, node: usize, sealed: SealedList) {
+        self.queues[node % MAX_NUMA_NODES].push(sealed);
+    }
+
+    /// Pops the entire queue of the given NUMA `node` or, if it is empty, the
+    /// first non-empty queue of any other node.
+    #[inline]
+    pub fn take_all(&self, node: usize) -> Iter {
+        let node = node % MAX_NUMA_NODES;
+        (0..MAX_NUMA_NODES)
+            .map(|offset| self.queues[(node + offset) % MAX_NUMA_NODES].take_all())
+            .find(|iter| !iter.is_empty())
+            .unwrap_or_else(Iter::empty)
+    }
+
+    /// Pops the entire queues of all NUMA nodes.
+    #[inline]
+    pub fn take_every(&self) -> impl Iterator<Item = <Iter as Iterator>::Item> + '_ {
+        self.queues.iter().flat_map(AbandonedQueue::take_all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::ptr::NonNull;
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    use debra_common::epoch::Epoch;
+
+    use crate::global;
+    use crate::sealed::SealedList;
+    use crate::Retired;
+
+    use super::{NumaQueues, MAX_NUMA_NODES, NUMA_NODE};
+
+    type BagPool = debra_common::bag::BagPool<crate::Debra>;
+    type EpochBagQueues = debra_common::bag::EpochBagQueues<crate::Debra>;
+
+    thread_local!(static MOCK_NODE: Cell<usize> = Cell::new(0));
+
+    struct DropCount<'a>(&'a AtomicUsize);
+    impl Drop for DropCount<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Relaxed);
+        }
+    }
+
+    fn mock_node() -> usize {
+        MOCK_NODE.with(|node| node.get())
+    }
+
+    fn sealed(counter: &'static AtomicUsize) -> SealedList {
+        let mut bags = EpochBagQueues::new();
+        let mut pool = BagPool::new();
+        let record = NonNull::from(Box::leak(Box::new(DropCount(counter))));
+        bags.retire_record(unsafe { Retired::new_unchecked(record) }, &mut pool);
+        SealedList::from_bags(bags, Epoch::new(), [1, 0, 0]).unwrap()
+    }
+
+    #[test]
+    fn prefer_own_node() {
+        static NODE_0: AtomicUsize = AtomicUsize::new(0);
+        static NODE_1: AtomicUsize = AtomicUsize::new(0);
+
+        let queues = NumaQueues::new();
+
+        MOCK_NODE.with(|node| node.set(1));
+        queues.push(mock_node(), sealed(&NODE_1));
+        MOCK_NODE.with(|node| node.set(0));
+        queues.push(mock_node(), sealed(&NODE_0));
+
+        // dropping the adopted bags reclaims their contents
+        queues.take_all(mock_node()).for_each(drop);
+        assert_eq!(NODE_0.load(Relaxed), 1);
+        assert_eq!(NODE_1.load(Relaxed), 0);
+
+        // the own queue is empty, so the queue of the other node is adopted
+        queues.take_all(mock_node()).for_each(drop);
+        assert_eq!(NODE_1.load(Relaxed), 1);
+    }
+
+    #[test]
+    fn node_hook() {
+        static NODE_1: AtomicUsize = AtomicUsize::new(0);
+        static NODE_2: AtomicUsize = AtomicUsize::new(0);
+
+        // threads of other tests remain on node 0, since their mock node is never changed
+        NUMA_NODE.init_once(|| mock_node);
+
+        // node ids beyond the maximum are mapped onto the available queues
+        MOCK_NODE.with(|node| node.set(MAX_NUMA_NODES + 1));
+        global::push_abandoned(sealed(&NODE_1));
+        MOCK_NODE.with(|node| node.set(2));
+        global::push_abandoned(sealed(&NODE_2));
+
+        MOCK_NODE.with(|node| node.set(MAX_NUMA_NODES + 2));
+        global::take_abandoned().for_each(drop);
+        assert_eq!(NODE_2.load(Relaxed), 1);
+        assert_eq!(NODE_1.load(Relaxed), 0);
+
+        MOCK_NODE.with(|node| node.set(1));
+        global::take_abandoned().for_each(drop);
+        assert_eq!(NODE_1.load(Relaxed), 1);
+    }
+}
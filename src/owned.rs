@@ -0,0 +1,35 @@
+//! Extensions for owned records of the DEBRA reclamation scheme.
+
+use core::mem;
+
+use crate::typenum::Unsigned;
+use crate::Owned;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// OwnedExt (trait)
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// An extension trait for [`Owned`] records of the [`Debra`][crate::Debra]
+/// reclamation scheme.
+///
+/// Since [`Owned`][reclaim::Owned] is defined in the `reclaim` crate, its
+/// methods can not be extended directly.
+pub trait OwnedExt {
+    /// Retires the record, deferring its drop until all threads, which are
+    /// currently active, have become inactive at least once.
+    ///
+    /// The record may be reclaimed by any other thread (e.g. after adopting
+    /// the records of an exited thread), hence it must be [`Send`].
+    fn retire(self);
+}
+
+/********** impl OwnedExt *************************************************************************/
+
+impl<T: Send + 'static, N: Unsigned> OwnedExt for Owned<T, N> {
+    #[inline]
+    fn retire(self) {
+        let unmarked = Owned::into_marked_non_null(self).decompose_non_null();
+        let retired = unsafe { crate::retired::typed::<T, _>(unmarked) };
+        crate::default::retire_sized(retired, mem::size_of::<T>());
+    }
+}
@@ -0,0 +1,205 @@
+//! Extensions for type-erased retired records.
+//!
+//! With the `debug-types` feature, every record retired through this crate is
+//! registered together with the name of its type by its address until it is
+//! reclaimed, so that the name can be queried for diagnostics (see
+//! [`RetiredExt::type_name`]).
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+use core::ptr::NonNull;
+
+#[cfg(feature = "debug-types")]
+use std::collections::HashMap;
+#[cfg(feature = "debug-types")]
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(feature = "debug-types")]
+use conquer_once::spin::OnceCell;
+
+use crate::Retired;
+
+/// The number of separately locked shards of the registry of type names, which
+/// reduces the contention between concurrently retiring threads.
+#[cfg(feature = "debug-types")]
+const TYPE_NAME_SHARDS: usize = 16;
+
+/// The type names of all pending [`Typed`] records by their addresses.
+#[cfg(feature = "debug-types")]
+static TYPE_NAMES: OnceCell<Vec<Mutex<HashMap<usize, &'static str>>>> = OnceCell::new();
+
+/// Creates a new [`Retired`] record from the given `record` and registers it
+/// with the name of `T` until it is reclaimed.
+///
+/// The record is retyped in place as a [`Typed`] record, so neither its address
+/// changes nor is any additional allocation required.
+///
+/// # Safety
+///
+/// The same safety requirements as for
+/// [`new_unchecked`][reclaim::Retired::new_unchecked] apply.
+#[cfg(feature = "debug-types")]
+#[inline]
+pub(crate) unsafe fn typed<T: ?Sized, R>(record: NonNull<R>) -> Retired {
+    let address = record.as_ptr() as usize;
+    type_names(address).insert(address, core::any::type_name::<T>());
+    Retired::new_unchecked(record.cast::<Typed<R>>())
+}
+
+/// Creates a new [`Retired`] record from the given `record`.
+///
+/// # Safety
+///
+/// The same safety requirements as for
+/// [`new_unchecked`][reclaim::Retired::new_unchecked] apply.
+#[cfg(not(feature = "debug-types"))]
+#[inline]
+pub(crate) unsafe fn typed<T: ?Sized, R>(record: NonNull<R>) -> Retired {
+    Retired::new_unchecked(record)
+}
+
+/// Creates a new [`Retired`] record from the given plain `boxed` value as by
+/// [`from_box`][RetiredExt::from_box] and registers it with the name of `T` as
+/// by [`typed`].
+#[inline]
+pub(crate) fn typed_box<T: ?Sized, R: 'static>(boxed: Box<R>) -> Retired {
+    unsafe { typed::<T, R>(NonNull::from(Box::leak(boxed))) }
+}
+
+/// Locks and returns the shard of the registry of type names responsible for
+/// `address`.
+#[cfg(feature = "debug-types")]
+#[inline]
+fn type_names(address: usize) -> MutexGuard<'static, HashMap<usize, &'static str>> {
+    let shards =
+        TYPE_NAMES.get_or_init(|| (0..TYPE_NAME_SHARDS).map(|_| Default::default()).collect());
+    // the lowest bits are usually zero due to the alignment of heap allocations
+    shards[(address >> 4) % TYPE_NAME_SHARDS].lock().unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Typed
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A record, whose type name is registered by its address until it is
+/// reclaimed.
+///
+/// Since a `Typed<R>` has exactly the same layout as an `R`, any record can be
+/// reclaimed as a `Typed` record, which first unregisters the type name and
+/// then drops the record itself.
+#[cfg(feature = "debug-types")]
+#[repr(transparent)]
+struct Typed<R>(R);
+
+/***** impl Drop **********************************************************************************/
+
+#[cfg(feature = "debug-types")]
+impl<R> Drop for Typed<R> {
+    #[inline]
+    fn drop(&mut self) {
+        let address = self as *const Self as usize;
+        type_names(address).remove(&address);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// RetiredExt (trait)
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// An extension trait for [`Retired`][reclaim::Retired] records of the
+/// [`Debra`][crate::Debra] reclamation scheme.
+///
+/// Since [`Retired`][reclaim::Retired] is defined in the `reclaim` crate, its
+/// methods can not be extended directly.
+pub trait RetiredExt {
+    /// Creates a new retired record from a plain `boxed` value, which has not
+    /// been allocated as part of a [`Record`][reclaim::Record] (e.g. by
+    /// [`Owned::new`][reclaim::Owned::new]).
+    ///
+    /// Unlike [`new_unchecked`][reclaim::Retired::new_unchecked], this is
+    /// safe, because the record header of [`Debra`][crate::Debra] is empty, so
+    /// that a `Record<T, Debra>` has exactly the same layout as a `T` and
+    /// reclaiming the record frees the box correctly.
+    fn from_box<T: 'static>(boxed: Box<T>) -> Self;
+
+    /// Consumes and reclaims (i.e. drops and de-allocates) the record
+    /// immediately.
+    ///
+    /// Unlike [`reclaim`][reclaim::Retired::reclaim], which only requires a
+    /// mutable reference, this takes ownership, so the record can not be
+    /// accessed (or reclaimed again) afterwards.
+    ///
+    /// # Safety
+    ///
+    /// The caller has to guarantee that no other thread can access the record
+    /// any more, i.e. there must not be any concurrent readers, which may
+    /// still hold a reference to it.
+    unsafe fn reclaim_now(self);
+
+    /// Returns the name of the type of the record, e.g. for identifying the
+    /// type of a record, whose `Drop` implementation panics during
+    /// reclamation.
+    ///
+    /// Only the types of records retired through this crate (e.g. by
+    /// [`retire`][reclaim::GlobalReclaim::retire] or
+    /// [`Debra::retire_box`][crate::Debra::retire_box]) are known, which
+    /// requires registering each of these records in a global registry.
+    /// For all other records, `"<unknown>"` is returned.
+    /// Records retired with [`retire_dyn`][crate::Debra::retire_dyn] report
+    /// the name of the unsized type and records retired with
+    /// [`retire_with_notify`][crate::Debra::retire_with_notify] the name of
+    /// the notified record's type.
+    /// As with [`core::any::type_name`], the returned name is only meant for
+    /// diagnostics and its exact format is not guaranteed.
+    ///
+    /// This requires Rust 1.38 or later.
+    #[cfg(feature = "debug-types")]
+    fn type_name(&self) -> &'static str;
+}
+
+/********** impl RetiredExt ***********************************************************************/
+
+impl RetiredExt for Retired {
+    #[inline]
+    fn from_box<T: 'static>(boxed: Box<T>) -> Self {
+        unsafe { Retired::new_unchecked(NonNull::from(Box::leak(boxed))) }
+    }
+
+    #[inline]
+    unsafe fn reclaim_now(mut self) {
+        self.reclaim();
+    }
+
+    #[cfg(feature = "debug-types")]
+    #[inline]
+    fn type_name(&self) -> &'static str {
+        let address = self.address();
+        type_names(address).get(&address).copied().unwrap_or("<unknown>")
+    }
+}
+
+#[cfg(all(test, feature = "debug-types"))]
+mod tests {
+    use crate::Retired;
+
+    use super::RetiredExt;
+
+    #[test]
+    fn type_name() {
+        let boxed = Box::new(1u32);
+        let address = &*boxed as *const u32 as usize;
+        let record = super::typed_box::<u32, _>(boxed);
+        // the record is registered by its own address without being wrapped
+        assert_eq!(record.address(), address);
+        assert_eq!(record.type_name(), "u32");
+
+        // the name is unregistered when the record is reclaimed
+        unsafe { record.reclaim_now() };
+        assert!(super::type_names(address).get(&address).is_none());
+
+        let untyped = Retired::from_box(Box::new(1u32));
+        assert_eq!(untyped.type_name(), "<unknown>");
+        unsafe { untyped.reclaim_now() };
+    }
+}
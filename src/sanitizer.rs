@@ -0,0 +1,148 @@
+//! Detection of grace period violations, which is only compiled with the
+//! `testing` feature.
+//!
+//! Every retired record is wrapped together with the epoch it was retired in.
+//! Right before the record is reclaimed, all registered threads are checked
+//! and, if any thread is still active in an epoch, from which the record may
+//! have been reachable, the reclamation is aborted with a panic.
+//! The wrapped records are counted as well, for verifying that all retired
+//! records are eventually reclaimed (see [`ReclamationFixture`][crate::testing::ReclamationFixture]),
+//! and registered until they are reclaimed, for dumping all pending records
+//! (see [`Debra::dump_pending`][crate::Debra::dump_pending]).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+
+use core::sync::atomic::{
+    AtomicU64,
+    Ordering::{Relaxed, SeqCst},
+};
+
+use conquer_once::spin::OnceCell;
+use debra_common::thread::State::Active;
+
+use crate::deferred::Deferred;
+use crate::global::{epoch_number, EPOCH, THREADS};
+use crate::testing::PendingRecord;
+use crate::{Retired, RetiredExt};
+
+/// The total number of sanitized records, which have been retired.
+static RETIRED: AtomicU64 = AtomicU64::new(0);
+/// The total number of sanitized records, which have been reclaimed.
+static RECLAIMED: AtomicU64 = AtomicU64::new(0);
+/// All sanitized records, which have been retired but not yet reclaimed, by
+/// their addresses.
+static PENDING: OnceCell<Mutex<HashMap<usize, PendingRecord>>> = OnceCell::new();
+
+/// Returns the total numbers of retired and reclaimed sanitized records.
+#[inline]
+pub(crate) fn counts() -> (u64, u64) {
+    (RETIRED.load(SeqCst), RECLAIMED.load(SeqCst))
+}
+
+/// Returns all sanitized records, which have been retired but not yet
+/// reclaimed, sorted by their retire epochs and addresses.
+#[inline]
+pub(crate) fn pending_records() -> Vec<PendingRecord> {
+    let mut records: Vec<_> = pending().values().copied().collect();
+    records.sort_by_key(|record| (record.retire_epoch, record.address));
+    records
+}
+
+/// Wraps the given `record` of `size` bytes, which is retired in
+/// `retire_epoch`, so that the grace period is checked right before it is
+/// reclaimed.
+#[inline]
+pub(crate) fn sanitized(mut record: Retired, size: usize, retire_epoch: u64) -> Retired {
+    RETIRED.fetch_add(1, Relaxed);
+    let address = record.address();
+    let thread_id = thread::current().id();
+    pending().insert(address, PendingRecord { address, size, retire_epoch, thread_id });
+
+    let deferred = Deferred::new(move || {
+        check_grace_period(address, retire_epoch, min_active_epoch());
+        pending().remove(&address);
+        unsafe { record.reclaim() };
+        RECLAIMED.fetch_add(1, Relaxed);
+    });
+
+    Retired::from_box(Box::new(deferred))
+}
+
+/// Wraps the final `record` of an exiting thread like [`sanitized`], but
+/// without counting it, since the final record of the last exiting thread is
+/// only reclaimed once another thread registers.
+#[inline]
+pub(crate) fn sanitized_final(mut record: Retired, retire_epoch: u64) -> Retired {
+    let deferred = Deferred::new(move || {
+        check_grace_period(record.address(), retire_epoch, min_active_epoch());
+        unsafe { record.reclaim() };
+    });
+
+    Retired::from_box(Box::new(deferred))
+}
+
+/// Locks and returns the registry of pending records.
+#[inline]
+fn pending() -> MutexGuard<'static, HashMap<usize, PendingRecord>> {
+    PENDING.get_or_init(Default::default).lock().unwrap()
+}
+
+/// Returns the oldest epoch announced by any currently active thread or
+/// `None`, if no thread is active.
+#[inline]
+fn min_active_epoch() -> Option<u64> {
+    THREADS
+        .iter()
+        .filter_map(|thread| match thread.load(SeqCst) {
+            (epoch, Active) => Some(epoch_number(epoch)),
+            _ => None,
+        })
+        .min()
+}
+
+/// Checks whether the record at `address`, which was retired in
+/// `retire_epoch`, may be reclaimed, when the oldest epoch announced by any
+/// active thread is `min_active`.
+///
+/// Threads, which were active when the record was unlinked, may have announced
+/// at most the epoch following `retire_epoch`, so the record must only be
+/// reclaimed once all active threads have announced a later epoch.
+///
+/// # Panics
+///
+/// Panics, if the record may still be accessed by some active thread.
+#[inline]
+fn check_grace_period(address: usize, retire_epoch: u64, min_active: Option<u64>) {
+    if let Some(min_active) = min_active {
+        assert!(
+            min_active >= retire_epoch + 2,
+            "grace period violation: record at {:#x} retired in epoch {} is reclaimed while a \
+             thread is still active in epoch {} (global epoch: {})",
+            address,
+            retire_epoch,
+            min_active,
+            epoch_number(EPOCH.load(SeqCst))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn check_grace_period() {
+        // no active threads or all active threads have announced a later epoch
+        super::check_grace_period(0x1000, 1, None);
+        super::check_grace_period(0x1000, 1, Some(3));
+        super::check_grace_period(0x1000, 1, Some(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "grace period violation: record at 0x1000 retired in epoch 1")]
+    fn premature_reclaim() {
+        // a buggy advance has ignored a thread, which is still active in the epoch following the
+        // one the record was retired in, so the record is reclaimed too early
+        super::check_grace_period(0x1000, 1, Some(2));
+    }
+}
@@ -8,6 +8,8 @@ use core::ptr::NonNull;
 use debra_common::arrayvec::ArrayVec;
 use debra_common::epoch::Epoch;
 
+use crate::local::BAG_QUEUE_COUNT;
+
 type BagNode = debra_common::bag::BagNode<crate::Debra>;
 type BagQueue = debra_common::bag::BagQueue<crate::Debra>;
 type EpochBagQueues = debra_common::bag::EpochBagQueues<crate::Debra>;
@@ -24,11 +26,19 @@ pub(crate) struct SealedList(NonNull<Sealed>, NonNull<Sealed>);
 /***** impl inherent ******************************************************************************/
 
 impl SealedList {
+    /// Seals all non-empty queues of `bags`, which contain the given numbers of
+    /// `records` (excluding any previously adopted bags) sorted by their age.
     #[inline]
-    pub fn from_bags(bags: EpochBagQueues, current_epoch: Epoch) -> Option<Self> {
+    pub fn from_bags(
+        bags: EpochBagQueues,
+        current_epoch: Epoch,
+        records: [usize; BAG_QUEUE_COUNT],
+    ) -> Option<Self> {
         let iter = ArrayVec::from(bags.into_sorted()).into_iter();
         iter.enumerate()
-            .filter_map(|(idx, queue)| Sealed::from_queue(queue, current_epoch - idx))
+            .filter_map(|(idx, queue)| {
+                Sealed::from_queue(queue, seal_epoch(current_epoch, idx), records[idx])
+            })
             .fold(None, |acc, tail| match acc {
                 Some(SealedList(head, mut prev_tail)) => {
                     unsafe { prev_tail.as_mut().next = Some(tail) };
@@ -44,6 +54,16 @@ impl SealedList {
     }
 }
 
+/// Returns the epoch for sealing the bag queue with the given `idx` (i.e. age)
+/// relative to the `current_epoch`.
+#[inline]
+fn seal_epoch(current_epoch: Epoch, idx: usize) -> Epoch {
+    // epochs wrap around, so the subtraction itself can not overflow, but any larger index would
+    // silently produce a wrong seal
+    debug_assert!(idx < BAG_QUEUE_COUNT, "invalid bag queue index {}", idx);
+    current_epoch - idx
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 // Sealed
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -55,15 +75,25 @@ pub(crate) struct Sealed {
     pub(crate) next: Option<NonNull<Sealed>>,
     pub(crate) seal: Epoch,
     queue: Box<BagNode>,
+    /// The number of records in the queue, excluding any adopted bags
+    #[cfg(feature = "stats")]
+    records: usize,
 }
 
 /***** impl inherent ******************************************************************************/
 
 impl Sealed {
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
     #[inline]
-    fn from_queue(queue: BagQueue, epoch: Epoch) -> Option<NonNull<Self>> {
+    fn from_queue(queue: BagQueue, epoch: Epoch, records: usize) -> Option<NonNull<Self>> {
         queue.into_non_empty().map(|queue| {
-            NonNull::from(Box::leak(Box::new(Self { next: None, seal: epoch, queue })))
+            NonNull::from(Box::leak(Box::new(Self {
+                next: None,
+                seal: epoch,
+                queue,
+                #[cfg(feature = "stats")]
+                records,
+            })))
         })
     }
 }
@@ -74,5 +104,42 @@ impl Drop for Sealed {
     #[inline]
     fn drop(&mut self) {
         unsafe { self.queue.reclaim_all() };
+        #[cfg(feature = "stats")]
+        crate::stats::record_reclaimed(self.records);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::NonNull;
+
+    use debra_common::epoch::{Epoch, PossibleAge};
+
+    use crate::abandoned::Iter;
+    use crate::Retired;
+
+    use super::SealedList;
+
+    type BagPool = debra_common::bag::BagPool<crate::Debra>;
+    type EpochBagQueues = debra_common::bag::EpochBagQueues<crate::Debra>;
+
+    #[test]
+    fn seal_epochs() {
+        let mut bags = EpochBagQueues::new();
+        let mut pool = BagPool::new();
+        let mut retire = |age| {
+            let record = NonNull::from(Box::leak(Box::new(0usize)));
+            let retired = unsafe { Retired::new_unchecked(record) };
+            bags.retire_record_by_age(retired, age, &mut pool);
+        };
+
+        retire(PossibleAge::SameEpoch);
+        retire(PossibleAge::OneEpoch);
+        retire(PossibleAge::TwoEpochs);
+
+        let epoch = Epoch::new() + 2;
+        let sealed = SealedList::from_bags(bags, epoch, [1, 1, 1]).unwrap();
+        let seals: Vec<_> = Iter::from(sealed).map(|sealed| sealed.seal).collect();
+        assert_eq!(seals, [epoch, epoch - 1, epoch - 2]);
     }
 }
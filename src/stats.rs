@@ -0,0 +1,69 @@
+//! Process-wide counters of retired and reclaimed records, which are only
+//! compiled with the `stats` feature.
+
+use core::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+use crate::global::THREADS;
+
+/// The total number of records retired by all threads.
+static RETIRED: AtomicU64 = AtomicU64::new(0);
+/// The total number of records reclaimed by all threads.
+static RECLAIMED: AtomicU64 = AtomicU64::new(0);
+
+/// Counts a retired record.
+#[inline]
+pub(crate) fn record_retired() {
+    RETIRED.fetch_add(1, Relaxed);
+}
+
+/// Counts the given number of `reclaimed` records.
+#[inline]
+pub(crate) fn record_reclaimed(reclaimed: usize) {
+    if reclaimed > 0 {
+        RECLAIMED.fetch_add(reclaimed as u64, Relaxed);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// ReclamationStats
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A snapshot of the process-wide reclamation statistics.
+///
+/// Records abandoned by exited threads are counted as reclaimed once they have
+/// been adopted and reclaimed by another thread.
+/// Records, which are reclaimed synchronously with the `debug-sync` feature or
+/// in global order with the `global-fifo` feature, are not counted.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReclamationStats {
+    /// The total number of records retired by all threads.
+    pub records_retired: u64,
+    /// The total number of records reclaimed by all threads.
+    pub records_reclaimed: u64,
+    /// The number of currently registered threads.
+    pub threads: usize,
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl ReclamationStats {
+    /// Takes a snapshot of the current process-wide counters.
+    #[inline]
+    pub(crate) fn load() -> Self {
+        Self {
+            records_retired: RETIRED.load(Relaxed),
+            records_reclaimed: RECLAIMED.load(Relaxed),
+            threads: THREADS.len(),
+        }
+    }
+
+    /// Returns the number of records, which have been retired but not yet
+    /// reclaimed, i.e. the amount of garbage currently buffered.
+    ///
+    /// Since the counters are loaded independently, this is only an estimate
+    /// while other threads retire or reclaim records concurrently.
+    #[inline]
+    pub fn pending(&self) -> u64 {
+        self.records_retired.saturating_sub(self.records_reclaimed)
+    }
+}
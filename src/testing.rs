@@ -0,0 +1,298 @@
+//! Utilities for testing data structures built on DEBRA, which are only
+//! compiled with the `testing` feature.
+
+use std::panic;
+use std::sync::{Mutex, MutexGuard};
+use std::thread::{self, JoinHandle, ThreadId};
+
+use conquer_once::spin::OnceCell;
+
+use crate::Debra;
+
+/// The default maximum number of grace periods for driving reclamation.
+const DEFAULT_MAX_GRACE_PERIODS: u32 = 16;
+/// The maximum number of attempts to advance the global epoch when checking
+/// for leaks.
+const LEAK_CHECK_ADVANCE_ATTEMPTS: u32 = 10_000;
+
+/// The lock serializing all fixture runs within a process.
+static FIXTURE_LOCK: OnceCell<Mutex<()>> = OnceCell::new();
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// ReclamationFixture
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A fixture for running a test scenario and verifying that all records
+/// retired during the scenario are eventually reclaimed.
+///
+/// Since the reclamation state is global, the retired and reclaimed records
+/// are counted process-wide:
+/// Fixture runs are serialized with each other, but no other threads must
+/// retire or reclaim any records during a run, otherwise the report is
+/// inaccurate.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use debra::testing::ReclamationFixture;
+/// use debra::{Owned, OwnedExt};
+///
+/// let report = ReclamationFixture::new().run(|| {
+///     let spawn = || {
+///         thread::spawn(|| {
+///             let owned: Owned<i32> = Owned::new(1);
+///             owned.retire();
+///         })
+///     };
+///
+///     (0..4).map(|_| spawn()).collect::<Vec<_>>()
+/// });
+///
+/// assert_eq!(report.retired, 4);
+/// report.assert_balanced();
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ReclamationFixture {
+    max_grace_periods: u32,
+}
+
+/***** impl Default *******************************************************************************/
+
+impl Default for ReclamationFixture {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl ReclamationFixture {
+    /// Creates a new [`ReclamationFixture`].
+    #[inline]
+    pub fn new() -> Self {
+        Self { max_grace_periods: DEFAULT_MAX_GRACE_PERIODS }
+    }
+
+    /// Sets the maximum number of grace periods, which are awaited for driving
+    /// the reclamation of all retired records to completion.
+    #[inline]
+    pub fn max_grace_periods(mut self, max_grace_periods: u32) -> Self {
+        self.max_grace_periods = max_grace_periods;
+        self
+    }
+
+    /// Runs the given `scenario`, joins all threads it returns and then drives
+    /// the reclamation of all records retired in the meantime to completion.
+    ///
+    /// Returns a report of the numbers of retired and reclaimed records.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the calling thread is active (i.e. has a live
+    /// [`Guard`][crate::Guard]), since reclamation could not be driven to
+    /// completion otherwise.
+    /// Panics of any of the joined threads are propagated.
+    pub fn run<I>(&self, scenario: impl FnOnce() -> I) -> ReclamationReport
+    where
+        I: IntoIterator<Item = JoinHandle<()>>,
+    {
+        assert!(!Debra::is_thread_active(), "fixture run with a live guard");
+
+        let _lock = lock();
+        let before = crate::sanitizer::counts();
+
+        for handle in scenario() {
+            if let Err(payload) = handle.join() {
+                panic::resume_unwind(payload);
+            }
+        }
+
+        let report = || report_since(before);
+
+        // the records of the calling thread and any adopted records of exited threads are
+        // reclaimed after at most two grace periods, but adopting records may take several more
+        for _ in 0..self.max_grace_periods {
+            if report().is_balanced() {
+                break;
+            }
+
+            Debra::synchronize_rcu();
+        }
+
+        report()
+    }
+}
+
+/// Returns a report of the records retired and reclaimed since the given
+/// counts were taken.
+#[inline]
+fn report_since((retired_before, reclaimed_before): (u64, u64)) -> ReclamationReport {
+    let (retired, reclaimed) = crate::sanitizer::counts();
+    ReclamationReport { retired: retired - retired_before, reclaimed: reclaimed - reclaimed_before }
+}
+
+/// Acquires the lock serializing all fixture runs, even if a previous run has
+/// panicked.
+#[inline]
+fn lock() -> MutexGuard<'static, ()> {
+    let lock = FIXTURE_LOCK.get_or_init(Mutex::default);
+    lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// LeakCheck
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A guard asserting that all records retired during its lifetime are
+/// eventually reclaimed, i.e. that no DEBRA-managed records are leaked.
+///
+/// Records are counted as they are retired and reclaimed rather than through
+/// the global allocator, since the allocator can not be replaced at runtime
+/// and each thread legitimately caches allocations (e.g. empty epoch bags)
+/// beyond the lifetime of any check.
+/// Like fixture runs, checks are serialized with each other and with fixture
+/// runs (so a fixture must not be run during a check), but no other threads
+/// must retire or reclaim any records during a check.
+///
+/// # Panics
+///
+/// When dropped, the check drives the reclamation of all records retired
+/// during its lifetime and panics, if any of them remain unreclaimed after a
+/// bounded number of attempts to advance the global epoch, e.g. because some
+/// thread (including the current one) remains active.
+/// Nothing is checked, if the thread is already panicking.
+///
+/// # Examples
+///
+/// ```
+/// use debra::testing::LeakCheck;
+/// use debra::{Owned, OwnedExt};
+///
+/// let _check = LeakCheck::new();
+/// let owned: Owned<i32> = Owned::new(1);
+/// owned.retire();
+/// // on drop, the check drives the reclamation of the record and asserts it has been reclaimed
+/// ```
+#[derive(Debug)]
+pub struct LeakCheck {
+    before: (u64, u64),
+    _lock: MutexGuard<'static, ()>,
+}
+
+/***** impl Default *******************************************************************************/
+
+impl Default for LeakCheck {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl LeakCheck {
+    /// Starts a new [`LeakCheck`], blocking while any other check or fixture
+    /// run is in progress.
+    #[inline]
+    pub fn new() -> Self {
+        let lock = lock();
+        Self { before: crate::sanitizer::counts(), _lock: lock }
+    }
+
+    /// Returns a report of the numbers of records retired and reclaimed since
+    /// the check was started.
+    #[inline]
+    pub fn report(&self) -> ReclamationReport {
+        report_since(self.before)
+    }
+}
+
+/***** impl Drop **********************************************************************************/
+
+impl Drop for LeakCheck {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            return;
+        }
+
+        // unlike `synchronize_rcu`, this never blocks on other threads remaining active
+        for _ in 0..LEAK_CHECK_ADVANCE_ATTEMPTS {
+            if self.report().is_balanced() {
+                break;
+            }
+
+            if !Debra::try_advance_epoch() {
+                thread::yield_now();
+            }
+        }
+
+        self.report().assert_balanced();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// ReclamationReport
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The numbers of retired and reclaimed records during a fixture run.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReclamationReport {
+    /// The number of records retired during the run.
+    pub retired: u64,
+    /// The number of records reclaimed during the run.
+    pub reclaimed: u64,
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl ReclamationReport {
+    /// Returns `true` if all retired records have been reclaimed.
+    #[inline]
+    pub fn is_balanced(&self) -> bool {
+        self.retired == self.reclaimed
+    }
+
+    /// Returns the number of retired records, which have not been reclaimed.
+    #[inline]
+    pub fn leaked(&self) -> u64 {
+        self.retired.saturating_sub(self.reclaimed)
+    }
+
+    /// Asserts that all retired records have been reclaimed.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any retired records have not been reclaimed.
+    #[inline]
+    pub fn assert_balanced(&self) {
+        assert!(
+            self.is_balanced(),
+            "{} of {} retired records have not been reclaimed ({} reclaimed)",
+            self.leaked(),
+            self.retired,
+            self.reclaimed
+        );
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// PendingRecord
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A retired record, which has not yet been reclaimed (see
+/// [`Debra::dump_pending`]).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PendingRecord {
+    /// The address of the record.
+    pub address: usize,
+    /// The size of the record in bytes or 0, if it was retired without a size
+    /// (e.g. through `LocalAccess::retire_record`).
+    pub size: usize,
+    /// The logical number of the epoch the record was retired in.
+    pub retire_epoch: u64,
+    /// The ID of the thread, which has retired the record.
+    pub thread_id: ThreadId,
+}
@@ -0,0 +1,180 @@
+//! Timestamps of global epoch advances for *std* environments.
+
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "testing")]
+use std::sync::RwLock;
+
+use conquer_once::spin::OnceCell;
+
+/// The type of a custom time source.
+#[cfg(feature = "testing")]
+type Clock = Box<dyn Fn() -> Instant + Send + Sync>;
+
+/// The minimum number of measured intervals between epoch advances required
+/// for estimating the reclamation delay.
+const MIN_SAMPLES: u64 = 4;
+/// The weight (as power of two) of previous intervals in the exponentially
+/// weighted moving average.
+const AVERAGE_SHIFT: u32 = 3;
+
+/// The point in time all timestamps are measured relative to.
+static ANCHOR: OnceCell<Instant> = OnceCell::new();
+/// The global record of epoch advances.
+static ADVANCES: AdvanceClock = AdvanceClock::new();
+/// The custom time source overriding [`Instant::now`].
+#[cfg(feature = "testing")]
+static CLOCK: OnceCell<RwLock<Option<Clock>>> = OnceCell::new();
+
+/// Overrides the time source used for all timestamps with the given `clock`.
+#[cfg(feature = "testing")]
+#[inline]
+pub(crate) fn set_clock(clock: impl Fn() -> Instant + Send + Sync + 'static) {
+    let lock = CLOCK.get_or_init(|| RwLock::new(None));
+    *lock.write().unwrap() = Some(Box::new(clock));
+}
+
+/// Records the current time as the time of the most recent successful global
+/// epoch advance.
+#[inline]
+pub(crate) fn record_advance() {
+    ADVANCES.record(now());
+}
+
+/// Returns the time elapsed since the most recent global epoch advance or since
+/// the first timestamp was taken, if the epoch has never been advanced.
+#[inline]
+pub(crate) fn since_last_advance() -> Duration {
+    ADVANCES.since_last(now())
+}
+
+/// Returns the estimated time until a record retired now will be reclaimed.
+#[inline]
+pub(crate) fn estimated_reclaim_delay() -> Option<Duration> {
+    ADVANCES.estimated_reclaim_delay()
+}
+
+/// Returns the nanoseconds elapsed since the global anchor point.
+#[inline]
+fn now() -> u64 {
+    let now = instant();
+    let anchor = *ANCHOR.get_or_init(|| now);
+    now.checked_duration_since(anchor).unwrap_or_default().as_nanos() as u64
+}
+
+/// Returns the current point in time.
+#[cfg(not(feature = "testing"))]
+#[inline]
+fn instant() -> Instant {
+    Instant::now()
+}
+
+/// Returns the current point in time according to the custom time source, if
+/// one is set.
+#[cfg(feature = "testing")]
+#[inline]
+fn instant() -> Instant {
+    match CLOCK.try_get().ok().and_then(|lock| lock.read().unwrap().as_ref().map(|clock| clock())) {
+        Some(instant) => instant,
+        None => Instant::now(),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// AdvanceClock
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A record of the timing of global epoch advances.
+///
+/// All timestamps are given in nanoseconds relative to an arbitrary anchor.
+/// Concurrent updates are not synchronized with each other, since the recorded
+/// values are only used for diagnostics.
+#[derive(Debug)]
+struct AdvanceClock {
+    /// The timestamp of the most recent epoch advance
+    last: AtomicU64,
+    /// The moving average of the intervals between epoch advances
+    average_interval: AtomicU64,
+    /// The total number of recorded epoch advances
+    count: AtomicU64,
+}
+
+/***** impl inherent ******************************************************************************/
+
+impl AdvanceClock {
+    /// Creates a new [`AdvanceClock`].
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            last: AtomicU64::new(0),
+            average_interval: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Records an epoch advance at the given timestamp.
+    #[inline]
+    fn record(&self, now: u64) {
+        let last = self.last.swap(now, Relaxed);
+        let samples = self.count.fetch_add(1, Relaxed);
+        if samples == 0 {
+            return;
+        }
+
+        let interval = now.saturating_sub(last);
+        let average = match samples {
+            1 => interval,
+            _ => {
+                let prev = self.average_interval.load(Relaxed);
+                (prev - (prev >> AVERAGE_SHIFT)) + (interval >> AVERAGE_SHIFT)
+            }
+        };
+
+        self.average_interval.store(average, Relaxed);
+    }
+
+    /// Returns the time elapsed between the most recent epoch advance and the
+    /// given timestamp.
+    #[inline]
+    fn since_last(&self, now: u64) -> Duration {
+        Duration::from_nanos(now.saturating_sub(self.last.load(Relaxed)))
+    }
+
+    /// Returns the estimated time until a record retired now will be
+    /// reclaimed, which is twice the average interval between epoch advances.
+    #[inline]
+    fn estimated_reclaim_delay(&self) -> Option<Duration> {
+        match self.count.load(Relaxed) {
+            count if count > MIN_SAMPLES => {
+                Some(Duration::from_nanos(2 * self.average_interval.load(Relaxed)))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::AdvanceClock;
+
+    #[test]
+    fn estimated_reclaim_delay() {
+        const INTERVAL: u64 = 10_000_000;
+
+        let clock = AdvanceClock::new();
+        let mut now = 0;
+
+        clock.record(now);
+        for _ in 0..super::MIN_SAMPLES {
+            assert_eq!(clock.estimated_reclaim_delay(), None);
+            now += INTERVAL;
+            clock.record(now);
+        }
+
+        assert_eq!(clock.estimated_reclaim_delay(), Some(Duration::from_nanos(2 * INTERVAL)));
+        assert_eq!(clock.since_last(now + INTERVAL), Duration::from_nanos(INTERVAL));
+    }
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const EPHEMERAL: usize = 100;
+
+#[test]
+fn retire_abandon() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let handles: Vec<_> = (0..EPHEMERAL)
+        .map(|_| {
+            thread::spawn(|| {
+                let atomic = Atomic::new(DropCount(&COUNTER));
+                let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+                unsafe { Debra::retire_abandon(unlinked) };
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // a single worker thread adopts and reclaims all records
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) < EPHEMERAL {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "abandoned records were never reclaimed");
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), EPHEMERAL);
+}
@@ -0,0 +1,35 @@
+#![cfg(debug_assertions)]
+
+use std::sync::atomic::Ordering::Relaxed;
+
+use debra::reclaim::GlobalReclaim;
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const WARN_THRESHOLD: u32 = 1_000;
+
+#[test]
+fn active_retire_warning() {
+    CONFIG.init_once(|| ConfigBuilder::new().active_retire_warn(WARN_THRESHOLD).build());
+
+    let atomic = Atomic::new(0);
+
+    // records retired under properly scoped guards never trigger the warning
+    for i in 0..2 * WARN_THRESHOLD {
+        let _guard = Guard::new();
+        let unlinked = atomic.swap(Owned::new(i), Relaxed).unwrap();
+        unsafe { Debra::retire(unlinked) };
+    }
+
+    assert_eq!(Debra::active_retire_warnings(), 0);
+
+    // retiring many records while continuously holding a guard triggers the warning once
+    let _guard = Guard::new();
+    for i in 0..2 * WARN_THRESHOLD {
+        let unlinked = atomic.swap(Owned::new(i), Relaxed).unwrap();
+        unsafe { Debra::retire(unlinked) };
+    }
+
+    assert_eq!(Debra::active_retire_warnings(), 1);
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+// spans several bag nodes for any bag size
+const BATCH: usize = 1_000;
+
+#[test]
+fn retire_batch() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let unlinked: Vec<_> =
+        (0..BATCH).map(|_| atomic.swap(Owned::new(DropCount(&COUNTER)), SeqCst).unwrap()).collect();
+
+    unsafe { Debra::retire_batch(unlinked) };
+    assert_eq!(Debra::bag_balance().iter().sum::<usize>(), BATCH);
+
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) < BATCH {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "batch was never reclaimed");
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), BATCH);
+}
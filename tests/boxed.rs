@@ -0,0 +1,88 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::reclaim::Retired;
+use debra::{ConfigBuilder, Debra, Guard, RetiredExt, CONFIG};
+
+use crate::common::DropCount;
+
+#[test]
+fn retire_box() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    // a node allocated as a plain box and accessed through a raw pointer, as in existing data
+    // structures not using `Owned`
+    let raw = Box::into_raw(Box::new(DropCount(&COUNTER)));
+    unsafe { Debra::retire_box(Box::from_raw(raw)) };
+
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) == 0 {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "boxed record was never reclaimed");
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), 1);
+}
+
+#[test]
+fn from_box() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // a plain box has no record header, which must neither be dropped nor de-allocated
+    let record = Retired::<Debra>::from_box(Box::new(DropCount(&COUNTER)));
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    unsafe { record.reclaim_now() };
+    assert_eq!(COUNTER.load(Relaxed), 1);
+}
+
+trait Shape {
+    fn sides(&self) -> usize;
+}
+
+struct Triangle(DropCount<'static>);
+impl Shape for Triangle {
+    fn sides(&self) -> usize {
+        3
+    }
+}
+
+struct Polygon(Vec<DropCount<'static>>);
+impl Shape for Polygon {
+    fn sides(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[test]
+fn retire_dyn() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Triangle(DropCount(&COUNTER))),
+        Box::new(Polygon((0..5).map(|_| DropCount(&COUNTER)).collect())),
+    ];
+    assert_eq!(shapes.iter().map(|shape| shape.sides()).sum::<usize>(), 8);
+
+    for shape in shapes {
+        unsafe { Debra::retire_dyn(shape) };
+    }
+    unsafe {
+        Debra::retire_dyn::<[DropCount]>(Box::new([DropCount(&COUNTER), DropCount(&COUNTER)]))
+    };
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    // every concrete type must be dropped through its own vtable
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) < 8 {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "boxed records were never reclaimed");
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), 8);
+}
@@ -0,0 +1,14 @@
+use debra::{Config, Debra};
+
+#[test]
+fn build_info() {
+    let info = Debra::build_info();
+    let config = Config::new();
+
+    assert_eq!(info.std, cfg!(feature = "std"));
+    assert_eq!(info.default_check_threshold, config.check_threshold());
+    assert_eq!(info.default_advance_threshold, config.advance_threshold());
+    assert_eq!(info.bag_queue_count, Debra::bag_balance().len());
+    assert!(info.bag_size > 0);
+    assert!(info.epoch_width_bits > 0 && info.epoch_width_bits <= usize::max_value().count_ones());
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Debra, Owned, OwnedExt, CONFIG};
+
+use crate::common::DropCount;
+
+const BUDGET: usize = 16 * 1024;
+const MAX_SIZE: usize = 1024;
+
+struct Record<A> {
+    _buf: A,
+    _count: DropCount<'static>,
+}
+
+#[test]
+fn byte_budget() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    // the thresholds are chosen so high, that the global epoch is never advanced incrementally
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(u32::max_value())
+            .advance_threshold(u32::max_value())
+            .reclaim_byte_budget(BUDGET)
+            .build()
+    });
+
+    let mut max_pending = 0;
+    for i in 0..10_000 {
+        match i % 3 {
+            0 => Owned::new(Record { _buf: [0u8; 64], _count: DropCount(&COUNTER) }).retire(),
+            1 => Owned::new(Record { _buf: [0u8; 256], _count: DropCount(&COUNTER) }).retire(),
+            _ => Owned::new(Record { _buf: [0u8; MAX_SIZE - 32], _count: DropCount(&COUNTER) })
+                .retire(),
+        }
+
+        max_pending = max_pending.max(Debra::pending_bytes());
+    }
+
+    // each forced reclamation only reclaims the oldest epoch bag queue, so the budget may be
+    // exceeded by a few records until the queue holding the bulk of the records is reclaimed
+    assert!(max_pending <= BUDGET + 3 * MAX_SIZE, "{} bytes pending", max_pending);
+    assert!(COUNTER.load(Relaxed) > 0);
+}
@@ -0,0 +1,210 @@
+#![cfg(feature = "chaos")]
+
+mod common;
+
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release},
+};
+use std::sync::Arc;
+use std::thread;
+
+use debra::{chaos, ConfigBuilder, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const SEEDS: u64 = 32;
+const THREADS: usize = 4;
+const OPERATIONS: usize = 1_000;
+
+struct Stack<T> {
+    head: Atomic<StackNode<T>>,
+}
+
+impl<T> Stack<T> {
+    fn new() -> Self {
+        Self { head: Atomic::null() }
+    }
+
+    fn push(&self, elem: T) {
+        let mut node =
+            Owned::new(StackNode { elem: ManuallyDrop::new(elem), next: Atomic::null() });
+        let guard = &Guard::new();
+
+        loop {
+            let head = self.head.load(Acquire, guard);
+            node.next.store(head, Relaxed);
+
+            match self.head.compare_exchange_weak(head, node, Release, Relaxed) {
+                Ok(_) => return,
+                Err(fail) => node = fail.input,
+            };
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        let guard = &Guard::new();
+
+        while let Some(head) = self.head.load(Acquire, guard) {
+            let next = head.next.load_unprotected(Relaxed);
+            if let Ok(unlinked) = self.head.compare_exchange_weak(head, next, Release, Relaxed) {
+                unsafe {
+                    let elem = ptr::read(&*unlinked.elem);
+                    unlinked.retire_unchecked();
+                    return Some(elem);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut curr = self.head.take();
+        while let Some(mut node) = curr {
+            unsafe { ManuallyDrop::drop(&mut node.elem) };
+            curr = node.next.take();
+        }
+    }
+}
+
+struct StackNode<T> {
+    elem: ManuallyDrop<T>,
+    next: Atomic<StackNode<T>>,
+}
+
+/// A Michael-Scott queue, the head always points at a sentinel node, whose
+/// element has either never been set or has already been moved out.
+struct Queue<T> {
+    head: Atomic<QueueNode<T>>,
+    tail: Atomic<QueueNode<T>>,
+}
+
+impl<T> Queue<T> {
+    fn new() -> Self {
+        let sentinel =
+            Owned::new(QueueNode { elem: ManuallyDrop::new(None), next: Atomic::null() });
+        let queue = Self { head: Atomic::null(), tail: Atomic::null() };
+        let guard = &Guard::new();
+        queue.head.store(sentinel, Relaxed);
+        queue.tail.store(queue.head.load(Relaxed, guard), Relaxed);
+        queue
+    }
+
+    fn push(&self, elem: T) {
+        let mut node =
+            Owned::new(QueueNode { elem: ManuallyDrop::new(Some(elem)), next: Atomic::null() });
+        let guard = &Guard::new();
+
+        loop {
+            let tail = self.tail.load(Acquire, guard).unwrap();
+            let next = tail.next.load(Acquire, guard);
+            if next.is_some() {
+                // help advancing the lagging tail
+                let _ = self.tail.compare_exchange(tail, next, Release, Relaxed);
+                continue;
+            }
+
+            match tail.next.compare_exchange(next, node, Release, Relaxed) {
+                Ok(_) => {
+                    let next = tail.next.load(Acquire, guard);
+                    let _ = self.tail.compare_exchange(tail, next, Release, Relaxed);
+                    return;
+                }
+                Err(fail) => node = fail.input,
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        let guard = &Guard::new();
+
+        loop {
+            let head = self.head.load(Acquire, guard).unwrap();
+            let next = head.next.load(Acquire, guard)?;
+
+            // the tail must never point at an unlinked node
+            let tail = self.tail.load(Acquire, guard).unwrap();
+            if ptr::eq(&*head, &*tail) {
+                let _ = self.tail.compare_exchange(tail, Some(next), Release, Relaxed);
+                continue;
+            }
+
+            if let Ok(unlinked) = self.head.compare_exchange(head, Some(next), Release, Relaxed) {
+                unsafe {
+                    let elem = ptr::read(&*next.elem);
+                    unlinked.retire_unchecked();
+                    return elem;
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        // the element of the sentinel node has already been moved out
+        let mut curr = self.head.take().and_then(|mut sentinel| sentinel.next.take());
+        while let Some(mut node) = curr {
+            unsafe { ManuallyDrop::drop(&mut node.elem) };
+            curr = node.next.take();
+        }
+    }
+}
+
+struct QueueNode<T> {
+    elem: ManuallyDrop<Option<T>>,
+    next: Atomic<QueueNode<T>>,
+}
+
+fn run<S: Send + Sync + 'static>(
+    structure: S,
+    push: fn(&S, DropCount<'static>),
+    pop: fn(&S) -> Option<DropCount<'static>>,
+    counter: &'static AtomicUsize,
+) {
+    let structure = Arc::new(structure);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let structure = Arc::clone(&structure);
+            thread::spawn(move || {
+                for _ in 0..OPERATIONS {
+                    push(&structure, DropCount(counter));
+                    let _ = pop(&structure);
+                    push(&structure, DropCount(counter));
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn seeded_stack_and_queue() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    for seed in 0..SEEDS {
+        static STACK_COUNTER: AtomicUsize = AtomicUsize::new(0);
+        static QUEUE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        chaos::configure(seed, 8);
+        STACK_COUNTER.store(0, Relaxed);
+        QUEUE_COUNTER.store(0, Relaxed);
+
+        run(Stack::new(), Stack::push, Stack::pop, &STACK_COUNTER);
+        run(Queue::new(), Queue::push, Queue::pop, &QUEUE_COUNTER);
+
+        let expected = THREADS * OPERATIONS * 2;
+        assert_eq!(STACK_COUNTER.load(Relaxed), expected, "stack failed with seed {}", seed);
+        assert_eq!(QUEUE_COUNTER.load(Relaxed), expected, "queue failed with seed {}", seed);
+    }
+}
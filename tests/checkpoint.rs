@@ -0,0 +1,28 @@
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+const OPS_PER_TRANSACTION: usize = 10;
+
+#[test]
+fn checkpoint_transactions() {
+    // the check threshold is far higher than the number of operations per transaction
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1_000).advance_threshold(0).build());
+
+    for _ in 0..5 {
+        let start = Debra::current_epoch();
+        for _ in 0..OPS_PER_TRANSACTION {
+            drop(Guard::new());
+        }
+
+        // without a checkpoint, the transaction does not reach the check threshold
+        Debra::checkpoint();
+        drop(Guard::new());
+        assert_eq!(start.wrapping_distance(Debra::current_epoch()), 1);
+    }
+
+    // while active, the checkpoint advances the epoch immediately
+    let guard = Guard::new();
+    let start = Debra::current_epoch();
+    Debra::checkpoint();
+    assert_eq!(start.wrapping_distance(Debra::current_epoch()), 1);
+    drop(guard);
+}
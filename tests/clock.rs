@@ -0,0 +1,34 @@
+#![cfg(feature = "testing")]
+
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+use std::time::{Duration, Instant};
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+const INTERVAL: Duration = Duration::from_millis(10);
+
+static OFFSET: AtomicU64 = AtomicU64::new(0);
+
+fn advance_clock(duration: Duration) {
+    OFFSET.fetch_add(duration.as_nanos() as u64, Relaxed);
+}
+
+#[test]
+fn fake_clock() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+    let base = Instant::now();
+    Debra::set_clock(move || base + Duration::from_nanos(OFFSET.load(Relaxed)));
+
+    // as the only registered thread, every new guard advances the global epoch
+    for _ in 0..10 {
+        advance_clock(INTERVAL);
+        let _guard = Guard::new();
+    }
+
+    assert_eq!(Debra::estimated_reclaim_delay(), Some(2 * INTERVAL));
+    assert!(Debra::reclamation_healthy(INTERVAL));
+
+    // without any further epoch advances, the reclamation becomes stale as the clock advances
+    advance_clock(2 * INTERVAL);
+    assert!(!Debra::reclamation_healthy(INTERVAL));
+}
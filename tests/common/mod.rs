@@ -0,0 +1,12 @@
+//! Helpers shared by the integration tests.
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// A record, which increments the referenced counter when it is dropped.
+pub struct DropCount<'a>(pub &'a AtomicUsize);
+
+impl Drop for DropCount<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Relaxed);
+    }
+}
@@ -0,0 +1,17 @@
+use debra::{ConfigBuilder, ConfigError};
+
+#[test]
+fn try_build() {
+    let config = ConfigBuilder::new().check_threshold(10).advance_threshold(0).try_build().unwrap();
+    assert_eq!((config.check_threshold(), config.advance_threshold()), (10, 0));
+
+    let err = ConfigBuilder::new().check_threshold(0).try_build().unwrap_err();
+    assert_eq!(err, ConfigError::ZeroCheckThreshold);
+    assert_eq!(err.to_string(), "the check threshold must be larger than 0");
+}
+
+#[test]
+#[should_panic(expected = "invalid configuration")]
+fn build_invalid() {
+    let _ = ConfigBuilder::new().check_threshold(0).build();
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "debug-sync")]
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{Debra, Guard};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+type Owned<T> = debra::Owned<T, debra::typenum::U0>;
+
+#[test]
+fn synchronous_reclaim() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // no reference to any retired record is used after its retirement
+    unsafe { Debra::set_synchronous_reclaim(true) };
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let guard = Guard::new();
+    for i in 1..=10 {
+        let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+        unsafe { unlinked.retire() };
+        // the record is dropped right away, despite the live guard
+        assert_eq!(COUNTER.load(Relaxed), i);
+    }
+
+    drop(guard);
+    unsafe { Debra::set_synchronous_reclaim(false) };
+
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+    unsafe { unlinked.retire() };
+    assert_eq!(COUNTER.load(Relaxed), 10);
+}
@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn defer() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    {
+        let guard = Guard::new();
+        guard.defer(|| {
+            COUNTER.fetch_add(1, Relaxed);
+        });
+        // a panicking closure must neither prevent the other closures from being invoked nor
+        // break any subsequent reclamation
+        guard.defer(|| panic!("deferred panic"));
+        guard.defer(|| {
+            COUNTER.fetch_add(1, Relaxed);
+        });
+        assert_eq!(COUNTER.load(Relaxed), 0);
+    }
+
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) < 2 {
+        drop(Guard::new());
+        guards += 1;
+        assert!(guards < 100_000, "deferred closures were never invoked");
+    }
+
+    // the closures are reclaimed together, so the panicking one has been invoked as well
+    assert_eq!(Debra::deferred_panics(), 1);
+
+    Guard::new().defer(|| {
+        COUNTER.fetch_add(1, Relaxed);
+    });
+
+    while COUNTER.load(Relaxed) < 3 {
+        drop(Guard::new());
+        guards += 1;
+        assert!(guards < 200_000, "deferred closure was never invoked after a panic");
+    }
+}
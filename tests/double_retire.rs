@@ -0,0 +1,14 @@
+#![cfg(all(debug_assertions, not(feature = "lifecycle-check"), not(feature = "global-fifo")))]
+
+use debra::Debra;
+
+#[test]
+#[should_panic(expected = "double retire")]
+fn double_retire() {
+    let raw = Box::into_raw(Box::new(1));
+    unsafe {
+        Debra::retire_box(Box::from_raw(raw));
+        // the same record is retired again before it could have been reclaimed
+        Debra::retire_box(Box::from_raw(raw));
+    }
+}
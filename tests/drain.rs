@@ -0,0 +1,33 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::thread;
+
+use debra::reclaim::GlobalReclaim;
+use debra::{Debra, Guard, Owned};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const RECORDS: usize = 100;
+
+#[test]
+fn drain_last_thread() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // the thread is the only one ever registered, so with the default thresholds it never gets to
+    // reclaim any of its records before exiting
+    thread::spawn(|| {
+        let atomic = Atomic::new(DropCount(&COUNTER));
+        let _guard = Guard::new();
+        for _ in 0..RECORDS {
+            let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+            unsafe { Debra::retire(unlinked) };
+        }
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(COUNTER.load(Relaxed), RECORDS);
+}
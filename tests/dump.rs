@@ -0,0 +1,55 @@
+#![cfg(feature = "testing")]
+
+use std::mem;
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, CONFIG};
+
+const THREADS: usize = 3;
+const RECORDS: usize = 10;
+
+type Record = [u64; 4];
+
+#[test]
+fn dump_pending() {
+    // the thresholds are never reached, so none of the records are reclaimed
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(u32::max_value())
+            .advance_threshold(u32::max_value())
+            .build()
+    });
+
+    let retired: Vec<_> = (0..THREADS)
+        .map(|_| {
+            thread::spawn(|| {
+                let addresses: Vec<_> = (0..RECORDS)
+                    .map(|_| {
+                        let record = Box::new(Record::default());
+                        let address = &*record as *const Record as usize;
+                        unsafe { Debra::retire_box(record) };
+                        address
+                    })
+                    .collect();
+
+                (thread::current().id(), addresses)
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    // the records of the exited threads remain pending in the abandoned queue
+    let dump = Debra::dump_pending();
+    for (thread_id, addresses) in retired {
+        for address in addresses {
+            let record = dump
+                .iter()
+                .find(|record| record.address == address)
+                .expect("retired record missing in dump");
+            assert_eq!(record.size, mem::size_of::<Record>());
+            assert_eq!(record.thread_id, thread_id);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+#[test]
+fn current_epoch() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let start = Debra::current_epoch();
+    assert_eq!(start.wrapping_distance(start), 0);
+    assert_eq!(format!("{}", start), format!("epoch {}", start.number()));
+
+    while start.wrapping_distance(Debra::current_epoch()) == 0 {
+        drop(Guard::new());
+    }
+
+    // a single thread can advance the global epoch only one step at a time
+    let mut prev = start;
+    for _ in 0..10 {
+        drop(Guard::new());
+        let curr = Debra::current_epoch();
+        assert!(prev.wrapping_distance(curr) <= 1);
+        prev = curr;
+    }
+
+    assert!(start.wrapping_distance(prev) >= 1);
+}
@@ -0,0 +1,27 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering::SeqCst};
+
+use debra::Debra;
+
+static ADVANCES: AtomicUsize = AtomicUsize::new(0);
+static LAST_OLD: AtomicU64 = AtomicU64::new(0);
+static LAST_NEW: AtomicU64 = AtomicU64::new(0);
+
+fn record_advance(old: u64, new: u64) {
+    LAST_OLD.store(old, SeqCst);
+    LAST_NEW.store(new, SeqCst);
+    ADVANCES.fetch_add(1, SeqCst);
+}
+
+#[test]
+fn on_epoch_advance() {
+    Debra::on_epoch_advance(record_advance);
+
+    let before = Debra::current_epoch();
+    // the current thread is the only registered and it is inactive
+    assert!(Debra::try_advance_epoch());
+
+    assert_eq!(ADVANCES.load(SeqCst), 1);
+    assert_eq!(LAST_OLD.load(SeqCst), before.number());
+    assert_eq!(LAST_NEW.load(SeqCst), before.number() + 1);
+    assert_eq!(Debra::current_epoch().number(), LAST_NEW.load(SeqCst));
+}
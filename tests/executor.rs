@@ -0,0 +1,51 @@
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, OwnedExt, CONFIG};
+
+const RECORDS: usize = 16;
+
+static SUBMITTED: AtomicUsize = AtomicUsize::new(0);
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+struct SlowDrop(ThreadId);
+impl Drop for SlowDrop {
+    fn drop(&mut self) {
+        thread::sleep(Duration::from_millis(10));
+        // destructors must not run on the retiring thread
+        assert_ne!(thread::current().id(), self.0);
+        DROPPED.fetch_add(1, Relaxed);
+    }
+}
+
+#[test]
+fn reclaim_executor() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let workers = Arc::new(Mutex::new(Vec::new()));
+    let handles = Arc::clone(&workers);
+    Debra::set_reclaim_executor(move |job| {
+        SUBMITTED.fetch_add(1, Relaxed);
+        handles.lock().unwrap().push(thread::spawn(job));
+    });
+
+    for _ in 0..RECORDS {
+        Owned::new(SlowDrop(thread::current().id())).retire();
+    }
+
+    let mut guards = 0;
+    while SUBMITTED.load(Relaxed) < RECORDS {
+        drop(Guard::new());
+        guards += 1;
+        assert!(guards < 100_000, "records were never offloaded");
+    }
+
+    let workers: Vec<_> = workers.lock().unwrap().drain(..).collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    assert_eq!(DROPPED.load(Relaxed), RECORDS);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use std::sync::atomic::{
+    AtomicBool, AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+use std::sync::Arc;
+use std::thread;
+
+use debra::{ConfigBuilder, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const SCANNERS: usize = 4;
+const EXITING: usize = 1_000;
+
+/// Threads continuously exit (removing their thread state from the global list and abandoning
+/// their bags) while other threads are scanning the list in order to advance the global epoch.
+#[test]
+fn exit_during_advance() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let done = Arc::new(AtomicBool::new(false));
+    let scanners: Vec<_> = (0..SCANNERS)
+        .map(|_| {
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                while !done.load(SeqCst) {
+                    let _guard = Guard::new();
+                }
+            })
+        })
+        .collect();
+
+    for _ in 0..EXITING {
+        thread::spawn(|| {
+            let atomic = Atomic::new(DropCount(&COUNTER));
+            let _guard = Guard::new();
+            let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+            unsafe { unlinked.retire() };
+        })
+        .join()
+        .unwrap();
+    }
+
+    done.store(true, SeqCst);
+    for scanner in scanners {
+        scanner.join().unwrap();
+    }
+
+    // all abandoned records are eventually adopted and reclaimed by the remaining thread
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) < EXITING {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 1_000_000, "abandoned records were never reclaimed");
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), EXITING);
+}
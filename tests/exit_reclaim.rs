@@ -0,0 +1,48 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::sync::mpsc;
+use std::thread;
+
+use debra::reclaim::GlobalReclaim;
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+#[test]
+fn reclaim_on_exit() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| {
+        ConfigBuilder::new().check_threshold(1).advance_threshold(0).min_abandon_records(16).build()
+    });
+
+    let (epoch_tx, epoch_rx) = mpsc::channel();
+    let (exit_tx, exit_rx) = mpsc::channel();
+
+    let handle = thread::spawn(move || {
+        let atomic = Atomic::new(DropCount(&COUNTER));
+        {
+            let _guard = Guard::new();
+            let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+            unsafe { Debra::retire(unlinked) };
+        }
+
+        epoch_tx.send(Debra::pending_by_epoch()[0].0).unwrap();
+        // the single pending record is safe by now and reclaimed in place when the thread exits
+        exit_rx.recv().unwrap();
+    });
+
+    // advance the global epoch three times, so the exiting thread can rotate all of its bags
+    let retire_epoch = epoch_rx.recv().unwrap();
+    while Debra::pending_by_epoch()[0].0 < retire_epoch + 3 {
+        let _guard = Guard::new();
+    }
+
+    exit_tx.send(()).unwrap();
+    handle.join().unwrap();
+
+    // the main thread has not created any guards since and hence not adopted any abandoned records
+    assert_eq!(COUNTER.load(Relaxed), 1);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "global-fifo")]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering::Relaxed};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use debra::{ConfigBuilder, Guard, Owned, OwnedExt, CONFIG};
+
+const THREADS: usize = 4;
+const PER_THREAD: usize = 100;
+
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+static OUT_OF_ORDER: AtomicBool = AtomicBool::new(false);
+
+struct DropOrder(usize);
+impl Drop for DropOrder {
+    fn drop(&mut self) {
+        if NEXT.fetch_add(1, Relaxed) != self.0 {
+            OUT_OF_ORDER.store(true, Relaxed);
+        }
+    }
+}
+
+#[test]
+fn global_retirement_order() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    // records are retired while holding the lock, so their ids match the global retirement order
+    let turn = Arc::new(Mutex::new(0));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let turn = Arc::clone(&turn);
+            thread::spawn(move || {
+                for _ in 0..PER_THREAD {
+                    let mut id = turn.lock().unwrap();
+                    let _guard = Guard::new();
+                    Owned::new(DropOrder(*id)).retire();
+                    *id += 1;
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let mut guards = 0;
+    while NEXT.load(Relaxed) < THREADS * PER_THREAD {
+        drop(Guard::new());
+        guards += 1;
+        assert!(guards <= 100_000, "retired records were never reclaimed");
+    }
+
+    assert!(!OUT_OF_ORDER.load(Relaxed));
+}
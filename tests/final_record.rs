@@ -0,0 +1,43 @@
+#![cfg(feature = "testing")]
+
+use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+use std::sync::Arc;
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+const SCANNERS: usize = 4;
+const EXITING: usize = 200;
+
+#[test]
+fn exit_while_scanning() {
+    // every guard performs an incremental check, so the scanners continuously traverse the
+    // thread list; reclaiming an unlinked entry too early is detected by the sanitizer
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let scanners: Vec<_> = (0..SCANNERS)
+        .map(|_| {
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                while !stop.load(SeqCst) {
+                    let _guard = Guard::new();
+                }
+            })
+        })
+        .collect();
+
+    for _ in 0..EXITING {
+        thread::spawn(|| {
+            let _guard = Guard::new();
+            unsafe { Debra::retire_box(Box::new(0u64)) };
+        })
+        .join()
+        .unwrap();
+    }
+
+    stop.store(true, SeqCst);
+    for scanner in scanners {
+        scanner.join().unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "testing")]
+
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use debra::testing::{LeakCheck, ReclamationFixture};
+use debra::{Guard, Owned, OwnedExt};
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+struct Stack<T> {
+    head: Atomic<Node<T>>,
+}
+
+impl<T> Stack<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self { head: Atomic::null() }
+    }
+
+    #[inline]
+    pub fn push(&self, elem: T) {
+        let mut node = Owned::new(Node::new(elem));
+        let guard = &Guard::new();
+
+        loop {
+            let head = self.head.load(Acquire, guard);
+            node.next.store(head, Relaxed);
+
+            match self.head.compare_exchange_weak(head, node, Release, Relaxed) {
+                Ok(_) => return,
+                Err(fail) => node = fail.input,
+            };
+        }
+    }
+
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        let guard = &Guard::new();
+
+        while let Some(head) = self.head.load(Relaxed, guard) {
+            let next = head.next.load_unprotected(Relaxed);
+            if let Ok(unlinked) = self.head.compare_exchange_weak(head, next, Release, Relaxed) {
+                unsafe {
+                    let elem = ptr::read(&*unlinked.elem);
+                    unlinked.retire_unchecked();
+                    return Some(elem);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let mut curr = self.head.take();
+        while let Some(mut node) = curr {
+            unsafe { ManuallyDrop::drop(&mut node.elem) };
+            curr = node.next.take();
+        }
+    }
+}
+
+struct Node<T> {
+    elem: ManuallyDrop<T>,
+    next: Atomic<Node<T>>,
+}
+
+impl<T> Node<T> {
+    #[inline]
+    fn new(elem: T) -> Self {
+        Self { elem: ManuallyDrop::new(elem), next: Atomic::null() }
+    }
+}
+
+#[test]
+fn treiber_stack_fixture() {
+    const THREADS: usize = 4;
+    const OPERATIONS: usize = 10_000;
+
+    let stack = Arc::new(Stack::new());
+    let report = ReclamationFixture::new().run(|| {
+        (0..THREADS)
+            .map(|id| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    for op in 0..OPERATIONS {
+                        stack.push(id * OPERATIONS + op);
+                        let _res = stack.pop();
+                    }
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // every pop, which has not found an empty stack, has retired exactly one node
+    let remaining = {
+        let mut remaining = 0;
+        while stack.pop().is_some() {
+            remaining += 1;
+        }
+        remaining
+    };
+
+    assert_eq!(report.retired as usize + remaining, THREADS * OPERATIONS);
+    report.assert_balanced();
+}
+
+#[test]
+fn treiber_stack_leak_check() {
+    const THREADS: usize = 4;
+    const OPERATIONS: usize = 10_000;
+
+    let check = LeakCheck::new();
+    let stack = Arc::new(Stack::new());
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || {
+                for op in 0..OPERATIONS {
+                    stack.push(id * OPERATIONS + op);
+                    let _res = stack.pop();
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    while stack.pop().is_some() {}
+    drop(check);
+}
+
+#[test]
+#[should_panic(expected = "have not been reclaimed")]
+fn leak_check_detects_leak() {
+    let (pinned_tx, pinned_rx) = mpsc::channel();
+    // dropped when unwinding from the expected panic, which releases the pinned thread
+    let (_release_tx, release_rx) = mpsc::channel::<()>();
+    let _check = LeakCheck::new();
+
+    thread::spawn(move || {
+        let _guard = Guard::new();
+        pinned_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+    });
+    pinned_rx.recv().unwrap();
+
+    // the pinned thread prevents the global epoch from advancing far enough to reclaim the record
+    let owned: Owned<i32> = Owned::new(1);
+    owned.retire();
+}
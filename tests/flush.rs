@@ -0,0 +1,74 @@
+mod common;
+
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+use std::thread;
+
+use debra::reclaim::GlobalReclaim;
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+fn init_config() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+}
+
+#[test]
+fn try_flush() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    init_config();
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), SeqCst).unwrap();
+    unsafe { unlinked.retire() };
+
+    // the current thread never creates a guard, so only `try_flush` can reclaim the record
+    let mut rounds = 0;
+    while COUNTER.load(Relaxed) == 0 {
+        thread::spawn(|| {
+            for _ in 0..100 {
+                let _guard = Guard::new();
+            }
+        })
+        .join()
+        .unwrap();
+
+        Debra::try_flush();
+        rounds += 1;
+        assert!(rounds < 1_000, "retired record was never reclaimed");
+    }
+
+    assert!(!Debra::is_thread_active());
+}
+
+#[test]
+fn try_flush_while_active() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    init_config();
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let guard = Guard::new();
+
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), SeqCst).unwrap();
+    unsafe { unlinked.retire() };
+
+    // other threads may attempt to advance the global epoch in the meantime
+    thread::spawn(|| {
+        for _ in 0..10_000 {
+            let _guard = Guard::new();
+        }
+    })
+    .join()
+    .unwrap();
+
+    Debra::try_flush();
+    assert!(Debra::is_thread_active());
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    drop(guard);
+    assert!(!Debra::is_thread_active());
+}
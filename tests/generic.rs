@@ -0,0 +1,224 @@
+//! A Treiber stack, which is generic over the reclamation scheme, as a template
+//! for reclaimer-agnostic data structures.
+//!
+//! The stack is instantiated both with [`Debra`] and with a `LeakReclaim`
+//! scheme, which never reclaims any retired records and serves as a baseline.
+
+mod common;
+
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering::{self, Acquire, Relaxed, Release},
+};
+use std::sync::Arc;
+use std::thread;
+
+use debra::reclaim::prelude::*;
+use debra::reclaim::{AcquireResult, MarkedPtr, NotEqualError};
+use debra::typenum::{Unsigned, U0};
+use debra::{ConfigBuilder, Debra, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T, R> = debra::reclaim::Atomic<T, R, U0>;
+type Owned<T, R> = debra::reclaim::Owned<T, R, U0>;
+
+struct Stack<T, R: GlobalReclaim> {
+    head: Atomic<Node<T, R>, R>,
+    dropped: &'static AtomicUsize,
+}
+
+impl<T, R: GlobalReclaim> Stack<T, R> {
+    #[inline]
+    pub fn new(dropped: &'static AtomicUsize) -> Self {
+        Self { head: Atomic::null(), dropped }
+    }
+
+    #[inline]
+    pub fn push(&self, elem: T) {
+        let mut node = Owned::new(Node::new(elem, self.dropped));
+        let mut guard = R::Guard::default();
+
+        loop {
+            let head = self.head.load(Acquire, &mut guard);
+            node.next.store(head, Relaxed);
+
+            match self.head.compare_exchange_weak(head, node, Release, Relaxed) {
+                Ok(_) => return,
+                Err(fail) => node = fail.input,
+            };
+        }
+    }
+
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        let mut guard = R::Guard::default();
+
+        while let Some(head) = self.head.load(Relaxed, &mut guard) {
+            let next = head.next.load_unprotected(Relaxed);
+            if let Ok(unlinked) = self.head.compare_exchange_weak(head, next, Release, Relaxed) {
+                unsafe {
+                    // the `Drop` code for T is never called for retired nodes, so it is
+                    // safe to use `retire_unchecked` and not require that `T: 'static`.
+                    let elem = ptr::read(&*unlinked.elem);
+                    unlinked.retire_unchecked();
+                    return Some(elem);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<T, R: GlobalReclaim> Drop for Stack<T, R> {
+    #[inline]
+    fn drop(&mut self) {
+        let mut curr = self.head.take();
+        while let Some(mut node) = curr {
+            unsafe { ManuallyDrop::drop(&mut node.elem) };
+            curr = node.next.take();
+        }
+    }
+}
+
+struct Node<T, R: GlobalReclaim> {
+    elem: ManuallyDrop<T>,
+    next: Atomic<Node<T, R>, R>,
+    _count: DropCount<'static>,
+}
+
+impl<T, R: GlobalReclaim> Node<T, R> {
+    #[inline]
+    fn new(elem: T, dropped: &'static AtomicUsize) -> Self {
+        Self { elem: ManuallyDrop::new(elem), next: Atomic::null(), _count: DropCount(dropped) }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// LeakReclaim
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A reclamation scheme, which leaks all retired records.
+#[derive(Debug, Default)]
+struct LeakReclaim;
+
+unsafe impl Reclaim for LeakReclaim {
+    type Local = ();
+    type RecordHeader = ();
+
+    #[inline]
+    unsafe fn retire_local<T: 'static, N: Unsigned>(_: &(), _: Unlinked<T, Self, N>) {}
+
+    #[inline]
+    unsafe fn retire_local_unchecked<T, N: Unsigned>(_: &(), _: Unlinked<T, Self, N>) {}
+}
+
+unsafe impl GlobalReclaim for LeakReclaim {
+    type Guard = LeakGuard;
+
+    #[inline]
+    fn try_flush() {}
+
+    #[inline]
+    unsafe fn retire<T: 'static, N: Unsigned>(_: Unlinked<T, Self, N>) {}
+
+    #[inline]
+    unsafe fn retire_unchecked<T, N: Unsigned>(_: Unlinked<T, Self, N>) {}
+}
+
+/// The guard of the [`LeakReclaim`] scheme, which never needs to protect
+/// anything, since no records are ever reclaimed.
+#[derive(Clone, Copy, Debug, Default)]
+struct LeakGuard;
+
+unsafe impl Protect for LeakGuard {
+    type Reclaimer = LeakReclaim;
+
+    #[inline]
+    fn release(&mut self) {}
+
+    #[inline]
+    fn protect<T, N: Unsigned>(
+        &mut self,
+        atomic: &debra::reclaim::Atomic<T, LeakReclaim, N>,
+        order: Ordering,
+    ) -> Marked<Shared<T, LeakReclaim, N>> {
+        unsafe { Marked::from_marked_ptr(atomic.load_raw(order)) }
+    }
+
+    #[inline]
+    fn protect_if_equal<T, N: Unsigned>(
+        &mut self,
+        atomic: &debra::reclaim::Atomic<T, LeakReclaim, N>,
+        expected: MarkedPtr<T, N>,
+        order: Ordering,
+    ) -> AcquireResult<T, LeakReclaim, N> {
+        match atomic.load_raw(order) {
+            ptr if ptr == expected => unsafe { Ok(Marked::from_marked_ptr(ptr)) },
+            _ => Err(NotEqualError),
+        }
+    }
+}
+
+unsafe impl ProtectRegion for LeakGuard {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+const THREADS: usize = 4;
+const OPERATIONS: usize = 10_000;
+
+/// Concurrently pushes and pops elements and then pops all remaining elements,
+/// so that all nodes have been retired, before dropping the stack.
+fn concurrent_push_pop<R: GlobalReclaim + Send + Sync + 'static>(nodes: &'static AtomicUsize) {
+    let stack: Arc<Stack<usize, R>> = Arc::new(Stack::new(nodes));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || {
+                for op in 0..OPERATIONS {
+                    stack.push(id * OPERATIONS + op);
+                    let _res = stack.pop();
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    while stack.pop().is_some() {}
+}
+
+#[test]
+fn debra_stack() {
+    static NODES: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    concurrent_push_pop::<Debra>(&NODES);
+
+    // nodes, which have not been reclaimed by the exited threads, have been abandoned and are
+    // eventually adopted and reclaimed by the current thread
+    let mut guards = 0;
+    while NODES.load(Relaxed) < THREADS * OPERATIONS {
+        let _guard = debra::Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "popped nodes were never reclaimed");
+    }
+
+    assert_eq!(NODES.load(Relaxed), THREADS * OPERATIONS);
+}
+
+#[test]
+fn leak_stack() {
+    static NODES: AtomicUsize = AtomicUsize::new(0);
+    concurrent_push_pop::<LeakReclaim>(&NODES);
+
+    // all popped nodes are leaked
+    assert_eq!(NODES.load(Relaxed), 0);
+}
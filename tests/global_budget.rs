@@ -0,0 +1,36 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Debra, CONFIG};
+
+use crate::common::DropCount;
+
+const BUDGET: usize = 1024;
+const RECORDS: usize = 100_000;
+
+/// A record large enough for the budget to hold only a few of them.
+struct Padded(DropCount<'static>, [u64; 8]);
+
+#[test]
+fn global_memory_budget() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    // the thresholds are never reached, so only the budget forces any reclamation
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(u32::max_value())
+            .advance_threshold(u32::max_value())
+            .global_memory_budget(BUDGET)
+            .build()
+    });
+
+    for _ in 0..RECORDS {
+        unsafe { Debra::retire_box(Box::new(Padded(DropCount(&COUNTER), [0; 8]))) };
+        let pending = Debra::global_pending_bytes();
+        assert!(pending <= BUDGET, "{} pending bytes exceed the budget", pending);
+    }
+
+    // all but the records fitting into the budget have been reclaimed synchronously
+    let size = std::mem::size_of::<Padded>();
+    assert!(COUNTER.load(Relaxed) >= RECORDS - BUDGET / size);
+}
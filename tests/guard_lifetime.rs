@@ -0,0 +1,36 @@
+#![cfg(debug_assertions)]
+
+use std::sync::atomic::Ordering::Relaxed;
+
+use debra::reclaim::GlobalReclaim;
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const WARN_THRESHOLD: u32 = 1_000;
+
+#[test]
+fn leaked_guard_warning() {
+    CONFIG.init_once(|| ConfigBuilder::new().guard_lifetime_warn(WARN_THRESHOLD).build());
+
+    let atomic = Atomic::new(0);
+
+    // short-lived guards never trigger the watchdog
+    for i in 0..WARN_THRESHOLD {
+        let _guard = Guard::new();
+        let unlinked = atomic.swap(Owned::new(i), Relaxed).unwrap();
+        unsafe { Debra::retire(unlinked) };
+    }
+
+    assert_eq!(Debra::leaked_guard_warnings(), 0);
+
+    // a single guard held across many operations triggers the watchdog once
+    let _leaked = Guard::new();
+    for i in 0..2 * WARN_THRESHOLD {
+        let _nested = Guard::new();
+        let unlinked = atomic.swap(Owned::new(i), Relaxed).unwrap();
+        unsafe { Debra::retire(unlinked) };
+    }
+
+    assert_eq!(Debra::leaked_guard_warnings(), 1);
+}
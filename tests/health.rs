@@ -0,0 +1,40 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+#[test]
+fn stalled_reclamation() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    for _ in 0..1_000 {
+        let _guard = Guard::new();
+    }
+
+    assert!(Debra::reclamation_healthy(Duration::from_secs(1)));
+
+    // pin another thread indefinitely, which stalls any further epoch advances
+    let (pinned_tx, pinned_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let handle = thread::spawn(move || {
+        let _guard = Guard::new();
+        pinned_tx.send(()).unwrap();
+        release_rx.recv().unwrap();
+    });
+
+    pinned_rx.recv().unwrap();
+    for _ in 0..1_000 {
+        let _guard = Guard::new();
+    }
+
+    thread::sleep(Duration::from_millis(200));
+    for _ in 0..1_000 {
+        let _guard = Guard::new();
+    }
+
+    assert!(!Debra::reclamation_healthy(Duration::from_millis(100)));
+
+    release_tx.send(()).unwrap();
+    handle.join().unwrap();
+}
@@ -0,0 +1,65 @@
+use std::alloc::{self, GlobalAlloc, Layout, System};
+use std::ptr::NonNull;
+use std::sync::atomic::{
+    AtomicBool, AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+/// The address of the allocation whose de-allocation is tracked.
+static TRACKED: AtomicUsize = AtomicUsize::new(0);
+/// The size and alignment of the layout the tracked allocation was de-allocated with.
+static DEALLOC_SIZE: AtomicUsize = AtomicUsize::new(0);
+static DEALLOC_ALIGN: AtomicUsize = AtomicUsize::new(0);
+static DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct TrackingAlloc;
+
+unsafe impl GlobalAlloc for TrackingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ptr as usize == TRACKED.load(SeqCst) {
+            DEALLOC_SIZE.store(layout.size(), SeqCst);
+            DEALLOC_ALIGN.store(layout.align(), SeqCst);
+        }
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: TrackingAlloc = TrackingAlloc;
+
+unsafe fn drop_buffer(ptr: NonNull<u8>) {
+    // the drop function must be called before the memory is de-allocated
+    assert_eq!(DEALLOC_SIZE.load(SeqCst), 0);
+    assert_eq!(*ptr.as_ptr(), 0xAB);
+    DROPPED.store(true, SeqCst);
+}
+
+#[test]
+fn retire_with_layout() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let layout = Layout::from_size_align(96, 32).unwrap();
+    let ptr = NonNull::new(unsafe { alloc::alloc(layout) }).unwrap();
+    unsafe { ptr.as_ptr().write_bytes(0xAB, layout.size()) };
+    TRACKED.store(ptr.as_ptr() as usize, SeqCst);
+
+    unsafe { Debra::retire_with_layout(ptr, layout, Some(drop_buffer)) };
+
+    let mut guards = 0;
+    while DEALLOC_SIZE.load(SeqCst) == 0 {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "the allocation was never de-allocated");
+    }
+
+    assert!(DROPPED.load(SeqCst));
+    assert_eq!(DEALLOC_SIZE.load(Relaxed), layout.size());
+    assert_eq!(DEALLOC_ALIGN.load(Relaxed), layout.align());
+}
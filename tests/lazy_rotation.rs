@@ -0,0 +1,33 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::reclaim::GlobalReclaim;
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+#[test]
+fn lazy_rotation() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| {
+        ConfigBuilder::new().check_threshold(1).advance_threshold(0).eager_rotation(false).build()
+    });
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+    unsafe { unlinked.retire() };
+
+    // the global epoch is advanced many times, but creating guards never reclaims anything
+    for _ in 0..100 {
+        let _guard = Guard::new();
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    // the deferred rotations are performed explicitly
+    Debra::try_flush();
+    assert_eq!(COUNTER.load(Relaxed), 1);
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Debra, CONFIG};
+
+use crate::common::DropCount;
+
+const MAX_RECORDS: usize = 64;
+const RECORDS: usize = 10_000;
+
+#[test]
+fn max_buffered_records() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    // the thresholds are never reached, so only the record limit forces any reclamation
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(u32::max_value())
+            .advance_threshold(u32::max_value())
+            .max_buffered_records(MAX_RECORDS)
+            .build()
+    });
+
+    for _ in 0..RECORDS {
+        unsafe { Debra::retire_box(Box::new(DropCount(&COUNTER))) };
+        // records retired in the last two epochs can not be reclaimed yet, so the limit may be
+        // exceeded by the few records retired since the forced advances
+        let pending: usize = Debra::bag_balance().iter().sum();
+        assert!(pending <= MAX_RECORDS + 3, "{} pending records exceed the limit", pending);
+    }
+
+    assert!(COUNTER.load(Relaxed) >= RECORDS - MAX_RECORDS - 3);
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "metrics")]
+
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+const THREADS: usize = 8;
+const GUARDS: usize = 100_000;
+
+#[test]
+fn contention_metrics() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+    let before = Debra::contention_metrics();
+
+    // all threads register at once and then attempt to advance the epoch with every guard
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                for _ in 0..GUARDS {
+                    let _guard = Guard::new();
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let after = Debra::contention_metrics();
+    assert!(after.advance_cas_losses > before.advance_cas_losses);
+    assert!(after.list_insert_retries >= before.list_insert_retries);
+    assert!(after.list_repeat_removes >= before.list_repeat_removes);
+}
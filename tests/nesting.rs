@@ -0,0 +1,25 @@
+use debra::Guard;
+
+#[test]
+fn outermost_guard() {
+    let outer = Guard::new();
+    assert!(outer.is_outermost());
+
+    let nested = Guard::new();
+    assert!(!nested.is_outermost());
+    // the outermost guard is only reported as such while no other guard is alive
+    assert!(!outer.is_outermost());
+
+    // clones only increment the count of the already active region
+    let clone = outer.clone();
+    assert!(!clone.is_outermost());
+
+    drop(outer);
+    drop(nested);
+    let still_nested = Guard::new();
+    assert!(!still_nested.is_outermost());
+
+    drop(clone);
+    drop(still_nested);
+    assert!(Guard::new().is_outermost());
+}
@@ -0,0 +1,51 @@
+#![cfg(not(feature = "std"))]
+#![no_std]
+
+extern crate std;
+
+use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+use std::alloc::{GlobalAlloc, Layout, System};
+
+use debra::LocalHandle;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct MockAlloc;
+
+unsafe impl GlobalAlloc for MockAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: MockAlloc = MockAlloc;
+
+fn nested(handle: LocalHandle) {
+    let guard = handle.guard();
+    assert_eq!(guard.pin_count(), 2);
+}
+
+#[test]
+fn local_handle() {
+    let before = ALLOCATIONS.load(SeqCst);
+    let handle = LocalHandle::register();
+    // the `Local` and its entry in the global thread list are allocated
+    assert!(ALLOCATIONS.load(SeqCst) > before);
+
+    {
+        let guard = handle.guard();
+        assert!(guard.is_outermost());
+        nested(handle);
+        assert_eq!(guard.pin_count(), 1);
+    }
+
+    assert_eq!(handle.local().guard_count(), 0);
+    unsafe { handle.deregister() };
+}
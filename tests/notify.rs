@@ -0,0 +1,37 @@
+mod common;
+
+use std::sync::atomic::{
+    AtomicBool, AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+#[test]
+fn retire_with_notify() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    static NOTIFIED: AtomicBool = AtomicBool::new(false);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+    unsafe {
+        Debra::retire_with_notify(unlinked, || {
+            // the record must already have been dropped
+            assert_eq!(COUNTER.load(Relaxed), 1);
+            NOTIFIED.store(true, SeqCst);
+        })
+    };
+
+    assert!(!NOTIFIED.load(SeqCst));
+    while COUNTER.load(Relaxed) == 0 {
+        let _guard = Guard::new();
+        assert!(!NOTIFIED.load(SeqCst) || COUNTER.load(Relaxed) == 1);
+    }
+
+    assert!(NOTIFIED.load(SeqCst));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "numa")]
+
+mod common;
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, OwnedExt, CONFIG, NUMA_NODE};
+
+use crate::common::DropCount;
+
+const NODES: usize = 2;
+const RECORDS: usize = 256;
+
+thread_local!(static NODE: Cell<usize> = Cell::new(0));
+
+fn current_node() -> usize {
+    NODE.with(|node| node.get())
+}
+
+#[test]
+fn reclaim_all_nodes() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    // the thresholds are never reached, so the epoch is never advanced and all records are
+    // abandoned by their exiting threads
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(u32::max_value())
+            .advance_threshold(u32::max_value())
+            .build()
+    });
+    NUMA_NODE.init_once(|| current_node);
+
+    // the calling thread remains registered, so the exiting threads can not reclaim their records
+    drop(Guard::new());
+
+    for node in 1..=NODES {
+        thread::spawn(move || {
+            NODE.with(|curr| curr.set(node));
+            for _ in 0..RECORDS {
+                Owned::new(DropCount(&COUNTER)).retire();
+            }
+        })
+        .join()
+        .unwrap();
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    // the abandoned records of both nodes are reclaimed, not only those of the first non-empty one
+    unsafe { Debra::reclaim_all_now() };
+    assert_eq!(COUNTER.load(Relaxed), NODES * RECORDS);
+}
@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, OwnedExt, CONFIG};
+
+/// The counter determining the order, in which records are dropped.
+static SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+struct DropOrder<'a>(&'a AtomicUsize);
+impl Drop for DropOrder<'_> {
+    fn drop(&mut self) {
+        self.0.store(SEQUENCE.fetch_add(1, Relaxed) + 1, Relaxed);
+    }
+}
+
+fn current_epoch() -> u64 {
+    Debra::pending_by_epoch()[0].0
+}
+
+#[test]
+fn oldest_epoch_first() {
+    static PARENT: AtomicUsize = AtomicUsize::new(0);
+    static CHILD: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let parent: Owned<_> = Owned::new(DropOrder(&PARENT));
+    parent.retire();
+
+    // the child is retired in a later epoch than the parent
+    let epoch = current_epoch();
+    while current_epoch() == epoch {
+        drop(Guard::new());
+    }
+
+    let child: Owned<_> = Owned::new(DropOrder(&CHILD));
+    child.retire();
+
+    let mut guards = 0;
+    while CHILD.load(Relaxed) == 0 {
+        drop(Guard::new());
+        guards += 1;
+        assert!(guards < 1_000, "retired records were never reclaimed");
+    }
+
+    // the parent has been dropped strictly before the child
+    assert!(PARENT.load(Relaxed) > 0);
+    assert!(PARENT.load(Relaxed) < CHILD.load(Relaxed));
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, OwnedExt, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+#[test]
+fn owned_retire() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let owned: Owned<_> = Owned::new(DropCount(&COUNTER));
+    owned.retire();
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) == 0 {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards <= 1_000, "retired record was never reclaimed");
+    }
+
+    assert!(guards >= 2);
+}
+
+#[test]
+fn unlinked_into_owned() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // single-threaded teardown: no other thread can hold a reference to the unlinked record
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+    let owned = unsafe { Debra::unlinked_into_owned(unlinked) };
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    drop(owned);
+    assert_eq!(COUNTER.load(Relaxed), 1);
+    drop(atomic.take());
+    assert_eq!(COUNTER.load(Relaxed), 2);
+}
@@ -0,0 +1,10 @@
+#[test]
+fn pin() {
+    assert!(!debra::is_pinned());
+    {
+        let _guard = debra::pin();
+        assert!(debra::is_pinned());
+    }
+
+    assert!(!debra::is_pinned());
+}
@@ -0,0 +1,16 @@
+use debra::Guard;
+
+#[test]
+fn pin_count() {
+    let outer = Guard::new();
+    assert_eq!(outer.pin_count(), 1);
+
+    {
+        let inner = Guard::new();
+        let clone = inner.clone();
+        assert_eq!(inner.pin_count(), 3);
+        assert_eq!(clone.pin_count(), 3);
+    }
+
+    assert_eq!(outer.pin_count(), 1);
+}
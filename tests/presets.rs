@@ -0,0 +1,28 @@
+use debra::Config;
+
+#[test]
+fn presets() {
+    let balanced = Config::balanced();
+    let low_latency = Config::low_latency();
+    let high_throughput = Config::high_throughput();
+    let low_memory = Config::low_memory();
+
+    for config in &[balanced, low_latency, high_throughput, low_memory] {
+        assert!(config.check_threshold() > 0);
+    }
+
+    // the balanced preset is the default configuration
+    assert_eq!(balanced.check_threshold(), Config::default().check_threshold());
+    assert_eq!(balanced.advance_threshold(), Config::default().advance_threshold());
+
+    // guard creation never reclaims records with low latency
+    assert!(!low_latency.eager_rotation());
+    assert!(balanced.eager_rotation());
+
+    // the global epoch is advanced more often for low memory usage than for high throughput
+    assert!(low_memory.check_threshold() < balanced.check_threshold());
+    assert!(balanced.check_threshold() < high_throughput.check_threshold());
+    assert!(low_memory.advance_threshold() < balanced.advance_threshold());
+    assert!(balanced.advance_threshold() < high_throughput.advance_threshold());
+    assert!(low_memory.min_abandon_records() > 0);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release},
+};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use debra::reclaim::prelude::*;
+use debra::{ConfigBuilder, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const BUCKETS: usize = 16;
+
+struct Bucket {
+    id: usize,
+    _count: DropCount<'static>,
+}
+
+#[test]
+fn protect_slice() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let buckets: Arc<Vec<Atomic<Bucket>>> = Arc::new(
+        (0..BUCKETS).map(|id| Atomic::new(Bucket { id, _count: DropCount(&COUNTER) })).collect(),
+    );
+
+    let guard = Guard::new();
+    let protected = guard.protect_slice(&buckets, Acquire);
+    assert_eq!(protected.len(), BUCKETS);
+
+    // replace and retire all buckets on another thread, which attempts to advance the epoch
+    let (tx, rx) = mpsc::channel();
+    let handle = {
+        let buckets = Arc::clone(&buckets);
+        thread::spawn(move || {
+            for (id, bucket) in buckets.iter().enumerate() {
+                let new = Owned::new(Bucket { id: id + BUCKETS, _count: DropCount(&COUNTER) });
+                let unlinked = bucket.swap(new, Release).unwrap();
+                unsafe { unlinked.retire() };
+            }
+
+            for _ in 0..1_000 {
+                let _guard = Guard::new();
+            }
+
+            tx.send(()).unwrap();
+        })
+    };
+
+    rx.recv().unwrap();
+
+    // the replaced buckets are still protected by the guard and can not have been reclaimed
+    assert_eq!(COUNTER.load(Relaxed), 0);
+    for (id, marked) in protected.into_iter().enumerate() {
+        match marked {
+            Marked::Value(shared) => assert_eq!(shared.id, id),
+            _ => panic!("bucket {} is null", id),
+        }
+    }
+
+    drop(guard);
+    handle.join().unwrap();
+
+    // once the guard is released, the replaced buckets are reclaimed eventually
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) < BUCKETS {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "replaced buckets were never reclaimed");
+    }
+}
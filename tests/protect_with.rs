@@ -0,0 +1,35 @@
+use std::sync::atomic::Ordering::{Acquire, Relaxed};
+
+use debra::reclaim::prelude::*;
+use debra::{Atomic, Guard};
+
+const LEN: usize = 8;
+
+fn slot(atomics: &[Atomic<usize>], key: usize) -> &Atomic<usize> {
+    &atomics[key.wrapping_mul(31) % atomics.len()]
+}
+
+#[test]
+fn protect_computed_address() {
+    let atomics: Vec<Atomic<usize>> = (0..LEN).map(Atomic::new).collect();
+    let guard = Guard::new();
+
+    for key in 0..LEN {
+        let expected = key.wrapping_mul(31) % LEN;
+        match unsafe { guard.protect_with(|| slot(&atomics, key).load_raw(Acquire)) } {
+            Marked::Value(shared) => assert_eq!(*shared, expected),
+            _ => panic!("protected pointer must not be null"),
+        }
+    }
+
+    let null: Atomic<usize> = Atomic::null();
+    match unsafe { guard.protect_with(|| null.load_raw(Relaxed)) } {
+        Marked::Null => {}
+        _ => panic!("protected pointer must be null"),
+    }
+
+    drop(guard);
+    for atomic in &atomics {
+        drop(atomic.take());
+    }
+}
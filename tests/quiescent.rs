@@ -0,0 +1,92 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::sync::{mpsc, Mutex, MutexGuard};
+use std::thread;
+
+use conquer_once::spin::OnceCell;
+use debra::{ConfigBuilder, Debra, Guard, Owned, OwnedExt, CONFIG};
+
+use crate::common::DropCount;
+
+const RECORDS: usize = 100;
+
+/// Both tests require the current thread to be the last active one, so they
+/// must not run concurrently.
+static SERIAL: OnceCell<Mutex<()>> = OnceCell::new();
+
+fn init() -> MutexGuard<'static, ()> {
+    // the thresholds are never reached, so only the quiescent gaps advance the global epoch
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(u32::max_value())
+            .advance_threshold(u32::max_value())
+            .quiescent_advance(true)
+            .build()
+    });
+
+    SERIAL.get_or_init(Default::default).lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[test]
+fn quiescent_gap() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let _serial = init();
+
+    // while another thread is active, the gap of the current thread is not quiescent
+    let (pinned_tx, pinned_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let guard = Guard::new();
+        pinned_tx.send(()).unwrap();
+        release_rx.recv().unwrap();
+        drop(guard);
+    });
+    pinned_rx.recv().unwrap();
+
+    {
+        let _guard = Guard::new();
+        for _ in 0..RECORDS {
+            Owned::new(DropCount(&COUNTER)).retire();
+        }
+    }
+    assert_eq!(COUNTER.load(Relaxed), 0);
+
+    // the other thread is the last one to become inactive and advances the epoch, but the records
+    // remain cached in the current thread's bags until it observes the advanced epoch
+    release_tx.send(()).unwrap();
+    handle.join().unwrap();
+
+    {
+        let _guard = Guard::new();
+        for _ in 0..RECORDS {
+            Owned::new(DropCount(&COUNTER)).retire();
+        }
+    }
+
+    // the quiescent gap after the last guard has reclaimed all records right away
+    assert_eq!(COUNTER.load(Relaxed), 2 * RECORDS);
+}
+
+#[test]
+fn quiescent_gap_after_rejoin() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let _serial = init();
+
+    // leaving and rejoining the scans must not count the thread as active twice
+    for _ in 0..3 {
+        let _guard = Guard::new();
+        unsafe { Debra::leave_scan() };
+        Debra::rejoin_scan();
+    }
+
+    {
+        let _guard = Guard::new();
+        for _ in 0..RECORDS {
+            Owned::new(DropCount(&COUNTER)).retire();
+        }
+    }
+
+    // the quiescent gap after the last guard has reclaimed all records right away
+    assert_eq!(COUNTER.load(Relaxed), RECORDS);
+}
@@ -0,0 +1,63 @@
+use std::sync::atomic::{
+    AtomicBool, AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release, SeqCst},
+};
+use std::sync::Arc;
+use std::thread;
+
+use debra::{Debra, Guard, Owned};
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const READERS: usize = 4;
+
+struct Buffer {
+    generation: usize,
+}
+
+#[test]
+fn synchronize_rcu() {
+    // the number of readers currently accessing the first (handed off) buffer
+    static READING_OLD: AtomicUsize = AtomicUsize::new(0);
+    static STOP: AtomicBool = AtomicBool::new(false);
+    // the default thresholds are kept, since the grace period must not depend on them
+
+    let shared = Arc::new(Atomic::new(Buffer { generation: 0 }));
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                while !STOP.load(Relaxed) {
+                    let guard = Guard::new();
+                    let buffer = shared.load(Acquire, &guard).unwrap();
+                    if buffer.generation == 0 {
+                        READING_OLD.fetch_add(1, SeqCst);
+                        for _ in 0..100 {
+                            assert_eq!(buffer.generation, 0);
+                            thread::yield_now();
+                        }
+                        READING_OLD.fetch_sub(1, SeqCst);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    // let the readers get hold of the first buffer
+    while READING_OLD.load(SeqCst) == 0 {
+        thread::yield_now();
+    }
+
+    let old = shared.swap(Owned::new(Buffer { generation: 1 }), Release).unwrap();
+    Debra::synchronize_rcu();
+
+    // after the grace period, no reader can still be accessing the old buffer, so it could be
+    // safely re-used
+    assert_eq!(READING_OLD.load(SeqCst), 0);
+    drop(old);
+
+    STOP.store(true, Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+use crate::common::DropCount;
+
+thread_local!(static ALLOCATED: Cell<usize> = Cell::new(0));
+
+struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // only the allocations of the current thread are counted
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns the number of bytes allocated by the current thread during `f`.
+fn allocated_by(f: impl FnOnce()) -> usize {
+    let before = ALLOCATED.with(Cell::get);
+    f();
+    ALLOCATED.with(Cell::get) - before
+}
+
+#[test]
+fn reader_only_thread() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    thread::spawn(|| {
+        // the thread is registered by its first guard
+        let reader = allocated_by(|| {
+            for _ in 0..1000 {
+                let _guard = Guard::new();
+            }
+        });
+
+        let record = Box::new(DropCount(&COUNTER));
+        let upgrade = allocated_by(|| unsafe { Debra::retire_box(record) });
+
+        // upon the first retire, all epoch bags are allocated at once, while readers allocate
+        // nothing but their registry entry
+        assert!(upgrade > 0, "first retire did not allocate any bags");
+        assert!(reader < upgrade / 3, "reader-only thread allocated epoch bags");
+
+        let mut guards = 0;
+        while COUNTER.load(Relaxed) == 0 {
+            let _guard = Guard::new();
+            guards += 1;
+            assert!(guards < 100_000, "record retired after upgrade was never reclaimed");
+        }
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(COUNTER.load(Relaxed), 1);
+}
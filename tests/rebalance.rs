@@ -0,0 +1,54 @@
+use std::sync::mpsc;
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, OwnedExt, CONFIG};
+
+const HEAVY_RECORDS: usize = 10_000;
+
+fn retire_many(count: usize) {
+    for _ in 0..count {
+        let owned: Owned<usize> = Owned::new(0);
+        owned.retire();
+    }
+}
+
+#[test]
+fn adopt_by_lighter_thread() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    // register the (light) current thread, which never retires any records itself
+    drop(Guard::new());
+
+    let (tx_heavy, rx_heavy) = mpsc::channel::<()>();
+    let (tx_done, rx_done) = mpsc::channel();
+    let heavy = thread::spawn(move || {
+        // wait until the records of the exiting thread have been abandoned
+        rx_heavy.recv().unwrap();
+
+        // the heavy thread rotates its bags many times, but always has far more pending records
+        // than the light thread, so it never adopts the abandoned records
+        for _ in 0..100 {
+            retire_many(HEAVY_RECORDS);
+            drop(Guard::new());
+        }
+
+        tx_done.send(()).unwrap();
+        // keep the thread alive until the light thread has adopted the abandoned records
+        let _ = rx_heavy.recv();
+    });
+
+    thread::spawn(|| retire_many(100)).join().unwrap();
+    tx_heavy.send(()).unwrap();
+    rx_done.recv().unwrap();
+
+    // the light thread has no pending records, so any counted records must have been adopted
+    let mut guards = 0;
+    while Debra::bag_balance().iter().sum::<usize>() == 0 {
+        drop(Guard::new());
+        guards += 1;
+        assert!(guards < 100_000, "abandoned records were never adopted by the light thread");
+    }
+
+    drop(tx_heavy);
+    heavy.join().unwrap();
+}
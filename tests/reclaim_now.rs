@@ -0,0 +1,32 @@
+mod common;
+
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::reclaim::Retired;
+use debra::{Debra, RetiredExt};
+
+use crate::common::DropCount;
+
+const RECORDS: usize = 10;
+
+#[test]
+fn reclaim_now() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // the record header of `Debra` is empty, so plain boxes can be turned into retired records
+    let records: Vec<Retired<Debra>> = (0..RECORDS)
+        .map(|_| {
+            let record = NonNull::from(Box::leak(Box::new(DropCount(&COUNTER))));
+            unsafe { Retired::new_unchecked(record) }
+        })
+        .collect();
+
+    for (reclaimed, record) in records.into_iter().enumerate() {
+        assert_eq!(COUNTER.load(Relaxed), reclaimed);
+        unsafe { record.reclaim_now() };
+        assert_eq!(COUNTER.load(Relaxed), reclaimed + 1);
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), RECORDS);
+}
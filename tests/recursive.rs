@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+const DEPTH: u32 = 10;
+const NODES: usize = (1 << DEPTH) - 1;
+const REENTRANT_LIMIT: usize = 128;
+const REENTRANT_RECLAIM_CAP: usize = 64;
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A binary tree node, which retires its children when it is reclaimed.
+struct Node {
+    children: Option<(Box<Node>, Box<Node>)>,
+}
+
+impl Node {
+    fn tree(depth: u32) -> Box<Self> {
+        let children = match depth {
+            1 => None,
+            _ => Some((Self::tree(depth - 1), Self::tree(depth - 1))),
+        };
+
+        Box::new(Self { children })
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        COUNTER.fetch_add(1, Relaxed);
+        if let Some((left, right)) = self.children.take() {
+            unsafe {
+                Debra::retire_box(left);
+                Debra::retire_box(right);
+            }
+        }
+    }
+}
+
+#[test]
+fn reclaim_deep_tree() {
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(1)
+            .advance_threshold(0)
+            .reentrant_limit(REENTRANT_LIMIT)
+            .reentrant_reclaim_cap(REENTRANT_RECLAIM_CAP)
+            .build()
+    });
+
+    unsafe { Debra::retire_box(Node::tree(DEPTH)) };
+
+    // children retired by their parents' destructors are deferred to later passes and at most
+    // `REENTRANT_RECLAIM_CAP` of them are retired into the same bag queue, which is reclaimed as a
+    // whole in a single pass
+    let mut passes = 0;
+    let mut max_per_pass = 0;
+    while COUNTER.load(Relaxed) < NODES {
+        let before = COUNTER.load(Relaxed);
+        drop(Guard::new());
+        max_per_pass = max_per_pass.max(COUNTER.load(Relaxed) - before);
+
+        passes += 1;
+        assert!(passes < 100_000, "tree was never completely reclaimed");
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), NODES);
+    assert!(
+        max_per_pass <= REENTRANT_RECLAIM_CAP,
+        "{} records reclaimed in one pass",
+        max_per_pass
+    );
+}
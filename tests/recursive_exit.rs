@@ -0,0 +1,48 @@
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+const LENGTH: usize = 100;
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A link of a chain, which retires its successor when it is reclaimed.
+struct Link {
+    next: Option<Box<Link>>,
+}
+
+impl Link {
+    fn chain(length: usize) -> Box<Self> {
+        (1..length)
+            .fold(Box::new(Self { next: None }), |next, _| Box::new(Self { next: Some(next) }))
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        COUNTER.fetch_add(1, Relaxed);
+        if let Some(next) = self.next.take() {
+            unsafe { Debra::retire_box(next) };
+        }
+    }
+}
+
+#[test]
+fn exit_with_recursive_records() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    // the only registered thread reclaims all of its records when it exits, the first link
+    // retires its successor while the thread local state is already being destroyed
+    thread::spawn(|| unsafe { Debra::retire_box(Link::chain(LENGTH)) }).join().unwrap();
+
+    // the successor has been abandoned and is adopted and reclaimed by the current thread
+    let mut passes = 0;
+    while COUNTER.load(Relaxed) < LENGTH {
+        drop(Guard::new());
+        passes += 1;
+        assert!(passes < 100_000, "chain was never completely reclaimed");
+    }
+
+    assert_eq!(COUNTER.load(Relaxed), LENGTH);
+}
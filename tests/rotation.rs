@@ -0,0 +1,21 @@
+#![cfg(feature = "testing")]
+
+use debra::{ConfigBuilder, Debra, Guard, CONFIG};
+
+#[test]
+fn bag_index_cycles() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let start = Debra::current_bag_index();
+    let rotations = Debra::rotations_performed();
+
+    for i in 1..=6 {
+        let epoch = Debra::pending_by_epoch()[0].0;
+        while Debra::pending_by_epoch()[0].0 == epoch {
+            drop(Guard::new());
+        }
+
+        assert_eq!(Debra::current_bag_index(), (start + i) % 3);
+        assert_eq!(Debra::rotations_performed(), rotations + i as u64);
+    }
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::sync::atomic::{
+    AtomicBool, AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+use std::sync::Arc;
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+#[test]
+fn leave_and_rejoin_scan() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let left = Arc::new(AtomicBool::new(false));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let handle = {
+        let left = Arc::clone(&left);
+        let done = Arc::clone(&done);
+        thread::spawn(move || {
+            let _guard = Guard::new();
+            unsafe { Debra::leave_scan() };
+            left.store(true, SeqCst);
+
+            // long computation without accessing any shared data
+            let mut acc = 0u64;
+            while !done.load(SeqCst) {
+                acc = acc.wrapping_add(1);
+            }
+
+            Debra::rejoin_scan();
+            assert!(Debra::is_thread_active());
+            acc
+        })
+    };
+
+    while !left.load(SeqCst) {}
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+    unsafe { unlinked.retire() };
+
+    // the global epoch advances freely although the other thread holds a guard
+    while COUNTER.load(Relaxed) == 0 {
+        let _guard = Guard::new();
+    }
+
+    done.store(true, SeqCst);
+    handle.join().unwrap();
+}
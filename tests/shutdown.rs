@@ -0,0 +1,103 @@
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release},
+};
+use std::sync::Arc;
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, CONFIG};
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+const THREADS: usize = 4;
+const ELEMS: usize = 1_000;
+
+/// The number of reclaimed stack nodes.
+static RECLAIMED: AtomicUsize = AtomicUsize::new(0);
+
+struct Stack<T> {
+    head: Atomic<Node<T>>,
+}
+
+impl<T> Stack<T> {
+    fn new() -> Self {
+        Self { head: Atomic::null() }
+    }
+
+    fn push(&self, elem: T) {
+        let mut node = Owned::new(Node { elem: ManuallyDrop::new(elem), next: Atomic::null() });
+        let guard = &Guard::new();
+
+        loop {
+            let head = self.head.load(Acquire, guard);
+            node.next.store(head, Relaxed);
+
+            match self.head.compare_exchange_weak(head, node, Release, Relaxed) {
+                Ok(_) => return,
+                Err(fail) => node = fail.input,
+            };
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        let guard = &Guard::new();
+
+        while let Some(head) = self.head.load(Relaxed, guard) {
+            let next = head.next.load_unprotected(Relaxed);
+            if let Ok(unlinked) = self.head.compare_exchange_weak(head, next, Release, Relaxed) {
+                unsafe {
+                    let elem = ptr::read(&*unlinked.elem);
+                    unlinked.retire_unchecked();
+                    return Some(elem);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+struct Node<T> {
+    elem: ManuallyDrop<T>,
+    next: Atomic<Node<T>>,
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        RECLAIMED.fetch_add(1, Relaxed);
+    }
+}
+
+#[test]
+fn reclaim_all_now() {
+    // the thresholds are never reached, so the nodes are only reclaimed at shutdown
+    CONFIG.init_once(|| {
+        ConfigBuilder::new()
+            .check_threshold(u32::max_value())
+            .advance_threshold(u32::max_value())
+            .build()
+    });
+
+    let stack = Arc::new(Stack::new());
+    let producers: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || {
+                for elem in 0..ELEMS {
+                    stack.push(elem);
+                    assert!(stack.pop().is_some());
+                }
+            })
+        })
+        .collect();
+
+    for producer in producers {
+        producer.join().unwrap();
+    }
+
+    // all producers have been joined and the calling thread is inactive
+    unsafe { Debra::reclaim_all_now() };
+    assert_eq!(RECLAIMED.load(Relaxed), THREADS * ELEMS);
+}
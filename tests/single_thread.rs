@@ -0,0 +1,29 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use debra::{ConfigBuilder, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+#[test]
+fn grace_period() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let atomic = Atomic::new(DropCount(&COUNTER));
+    let unlinked = atomic.swap(Owned::new(DropCount(&COUNTER)), Relaxed).unwrap();
+    unsafe { unlinked.retire() };
+
+    // each guard advances the global epoch, but the record must survive at least two advances
+    let mut guards = 0;
+    while COUNTER.load(Relaxed) == 0 {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards <= 10, "retired record was never reclaimed");
+    }
+
+    assert!(guards >= 3, "retired record was reclaimed after only {} guards", guards);
+}
@@ -0,0 +1,32 @@
+use std::sync::atomic::Ordering::{Acquire, Relaxed};
+
+use debra::reclaim::prelude::*;
+use debra::typenum::U2;
+use debra::{Guard, Owned};
+
+type Atomic<T> = debra::Atomic<T, U2>;
+
+const TAG: usize = 0b10;
+
+#[test]
+fn snapshot() {
+    let atomic: Atomic<i32> = Atomic::null();
+    let guard = Guard::new();
+
+    let snapshot = guard.snapshot(&atomic, Acquire);
+    assert!(snapshot.shared.is_none());
+    assert_eq!(snapshot.tag, 0);
+    assert!(snapshot.raw.is_null());
+
+    atomic.store(Owned::compose(Owned::new(1), TAG), Relaxed);
+
+    let snapshot = guard.snapshot(&atomic, Acquire);
+    let shared = snapshot.shared.unwrap();
+    assert_eq!(*shared, 1);
+    assert_eq!(snapshot.tag, TAG);
+    assert_eq!(snapshot.raw.decompose_tag(), TAG);
+    assert_eq!(shared.as_marked_ptr(), snapshot.raw);
+
+    drop(guard);
+    assert_eq!(*atomic.take().unwrap(), 1);
+}
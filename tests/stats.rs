@@ -0,0 +1,40 @@
+#![cfg(feature = "stats")]
+
+use std::thread;
+
+use debra::{ConfigBuilder, Debra, Guard, Owned, OwnedExt, CONFIG};
+
+const RECORDS: u64 = 100;
+
+#[test]
+fn stats() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+
+    let before = Debra::stats();
+    for _ in 0..RECORDS {
+        Owned::new(1).retire();
+    }
+
+    // the records of an exiting thread are abandoned and adopted by the current thread
+    thread::spawn(|| {
+        for _ in 0..RECORDS {
+            Owned::new(1).retire();
+        }
+    })
+    .join()
+    .unwrap();
+
+    let stats = Debra::stats();
+    assert!(stats.threads >= 1);
+    assert!(stats.records_retired - before.records_retired >= 2 * RECORDS);
+
+    let mut guards = 0;
+    while Debra::stats().pending() > 0 {
+        drop(Guard::new());
+        guards += 1;
+        assert!(guards < 100_000, "{} records were never reclaimed", Debra::stats().pending());
+    }
+
+    let stats = Debra::stats();
+    assert_eq!(stats.records_retired, stats.records_reclaimed);
+}
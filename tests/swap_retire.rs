@@ -0,0 +1,52 @@
+mod common;
+
+use std::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+
+use debra::{AtomicExt, ConfigBuilder, Guard, Owned, CONFIG};
+
+use crate::common::DropCount;
+
+type Atomic<T> = debra::Atomic<T, debra::typenum::U0>;
+
+fn init_config() {
+    CONFIG.init_once(|| ConfigBuilder::new().check_threshold(1).advance_threshold(0).build());
+}
+
+fn reclaim_until(counter: &AtomicUsize, count: usize) {
+    let mut guards = 0;
+    while counter.load(Relaxed) < count {
+        let _guard = Guard::new();
+        guards += 1;
+        assert!(guards < 100_000, "swapped record was never reclaimed");
+    }
+}
+
+#[test]
+fn swap_and_retire() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    init_config();
+
+    let atomic = Atomic::null();
+    // swapping out a null pointer retires nothing
+    unsafe { atomic.swap_and_retire(Owned::new(DropCount(&COUNTER)), SeqCst) };
+    unsafe { atomic.swap_and_retire(Owned::new(DropCount(&COUNTER)), SeqCst) };
+
+    reclaim_until(&COUNTER, 1);
+    assert_eq!(COUNTER.load(Relaxed), 1);
+}
+
+#[test]
+fn swap_and_retire_unchecked() {
+    let counter = AtomicUsize::new(0);
+    init_config();
+
+    let atomic = Atomic::new(DropCount(&counter));
+    unsafe { atomic.swap_and_retire_unchecked(Owned::new(DropCount(&counter)), SeqCst) };
+
+    reclaim_until(&counter, 1);
+    drop(atomic.take());
+    assert_eq!(counter.load(Relaxed), 2);
+}
@@ -0,0 +1,15 @@
+use std::mem;
+
+use debra::Debra;
+
+#[repr(align(64))]
+struct CacheAligned(u8);
+
+#[test]
+fn max_tag_bits() {
+    assert_eq!(Debra::max_tag_bits::<u8>(), 0);
+    assert_eq!(Debra::max_tag_bits::<u16>(), 1);
+    assert_eq!(Debra::max_tag_bits::<u32>(), 2);
+    assert_eq!(Debra::max_tag_bits::<usize>(), mem::align_of::<usize>().trailing_zeros());
+    assert_eq!(Debra::max_tag_bits::<CacheAligned>(), 6);
+}
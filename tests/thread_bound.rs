@@ -0,0 +1,30 @@
+use debra::reclaim::GlobalReclaim;
+use debra::{Debra, Guard};
+use static_assertions::assert_not_impl_any;
+
+// the guard count of the thread local state is not synchronized
+assert_not_impl_any!(Guard: Send, Sync);
+
+#[test]
+fn interleaved_guards_and_flush() {
+    Debra::try_flush();
+    assert!(!Debra::is_thread_active());
+
+    let outer = Guard::new();
+    Debra::try_flush();
+    let inner = outer.clone();
+    Debra::try_flush();
+    assert!(Debra::is_thread_active());
+
+    drop(outer);
+    Debra::try_flush();
+    assert!(Debra::is_thread_active());
+
+    drop(inner);
+    assert!(!Debra::is_thread_active());
+    Debra::try_flush();
+    assert!(!Debra::is_thread_active());
+
+    let _guard = Guard::new();
+    assert!(Debra::is_thread_active());
+}
@@ -0,0 +1,27 @@
+use std::sync::mpsc;
+use std::thread;
+
+use debra::{Debra, Guard};
+
+#[test]
+fn thread_count() {
+    // registers the current thread
+    drop(Guard::new());
+    let before = Debra::thread_count();
+    assert!(before >= 1);
+
+    let (registered_tx, registered_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let handle = thread::spawn(move || {
+        drop(Guard::new());
+        registered_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+    });
+
+    registered_rx.recv().unwrap();
+    assert_eq!(Debra::thread_count(), before + 1);
+
+    drop(release_tx);
+    handle.join().unwrap();
+    assert_eq!(Debra::thread_count(), before);
+}
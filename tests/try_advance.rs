@@ -0,0 +1,37 @@
+use std::sync::mpsc;
+use std::thread;
+
+use debra::{Debra, Guard};
+
+#[test]
+fn try_advance_epoch() {
+    let start = Debra::current_epoch();
+    assert!(Debra::try_advance_epoch());
+    assert_eq!(start.wrapping_distance(Debra::current_epoch()), 1);
+
+    {
+        let _guard = Guard::new();
+        assert!(!Debra::try_advance_epoch(), "active threads must not advance the epoch");
+    }
+
+    let (pinned_tx, pinned_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let guard = Guard::new();
+        pinned_tx.send(()).unwrap();
+        release_rx.recv().unwrap();
+        drop(guard);
+    });
+
+    pinned_rx.recv().unwrap();
+    // the other thread has announced the current epoch, so it can be advanced once more
+    let pinned = Debra::current_epoch();
+    assert!(Debra::try_advance_epoch());
+    // ... but now the other thread lags behind
+    assert!(!Debra::try_advance_epoch());
+    assert_eq!(pinned.wrapping_distance(Debra::current_epoch()), 1);
+
+    release_tx.send(()).unwrap();
+    handle.join().unwrap();
+    assert!(Debra::try_advance_epoch());
+}